@@ -0,0 +1,92 @@
+//! Checking and fixing day 05 updates with the rule comparator, against the
+//! pairwise scan it replaced, on synthetic updates of a few hundred pages.
+//!
+//! Run with `cargo bench --bench day05`.
+
+#![feature(test)]
+
+extern crate test;
+
+use adventofcode_2024::days::day05::OrderingRules;
+use test::{black_box, Bencher};
+
+const PAGES: u32 = 500;
+
+/// Every pair of pages ordered by number, as in a real input where each
+/// update has a rule for every pair.
+fn rules() -> OrderingRules {
+    OrderingRules::new((1..PAGES).flat_map(|a| (a + 1..=PAGES).map(move |b| (a, b))))
+}
+
+/// `len` distinct pages in ascending, and so valid, order.
+fn sorted_update(len: usize) -> Vec<u32> {
+    (1..=PAGES)
+        .step_by(PAGES as usize / len)
+        .take(len)
+        .collect()
+}
+
+/// The same pages shuffled with a fixed linear congruential generator.
+fn shuffled_update(len: usize) -> Vec<u32> {
+    let mut pages = sorted_update(len);
+    let mut state = 0x2545_f491_u64;
+    for i in (1..pages.len()).rev() {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        pages.swap(i, (state >> 33) as usize % (i + 1));
+    }
+    pages
+}
+
+/// The quadratic check part 1 used before the comparator.
+fn pairwise_is_ordered(rules: &OrderingRules, pages: &[u32]) -> bool {
+    for (idx, up) in pages[..pages.len() - 1].iter().enumerate() {
+        for down in pages[idx + 1..].iter() {
+            if !rules.has_rule(*up, *down) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+#[bench]
+fn check_pairwise(b: &mut Bencher) {
+    let rules = rules();
+    let update = sorted_update(250);
+    b.iter(|| pairwise_is_ordered(&rules, black_box(&update)));
+}
+
+#[bench]
+fn check_comparator(b: &mut Bencher) {
+    let rules = rules();
+    let update = sorted_update(250);
+    assert_eq!(
+        rules.is_sorted(&update),
+        pairwise_is_ordered(&rules, &update)
+    );
+    b.iter(|| rules.is_sorted(black_box(&update)));
+}
+
+/// Topological sort over the rules between the pages.
+#[bench]
+fn fix_total_order(b: &mut Bencher) {
+    let rules = rules();
+    let update = shuffled_update(250);
+    b.iter(|| rules.total_order(black_box(&update)));
+}
+
+/// Sort by the number of pages that must directly precede each one, checked
+/// with the comparator.
+#[bench]
+fn fix_comparator(b: &mut Bencher) {
+    let rules = rules();
+    let update = shuffled_update(250);
+    let mut sorted = update.clone();
+    rules.sort(&mut sorted);
+    assert_eq!(Some(sorted), rules.total_order(&update));
+    b.iter(|| {
+        let mut pages = black_box(&update).clone();
+        rules.sort(&mut pages);
+        pages
+    });
+}
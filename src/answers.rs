@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use winnow::ascii::{dec_uint, digit1, line_ending, space0};
+use winnow::combinator::{
+    alt, cut_err, delimited, not, opt, preceded, repeat, repeat_till, separated_pair, terminated,
+};
+use winnow::error::{StrContext, StrContextValue};
+use winnow::prelude::*;
+use winnow::token::take_till;
+
+use crate::error::{Error, ParseError, Result};
+use crate::input::{self, Input};
+use crate::prelude::*;
+use crate::solution::{Answer, Part};
+
+/// Accepted answers keyed by day and part, as recorded in `data/answers.toml`:
+///
+/// ```toml
+/// [day01]
+/// part1 = 2196996
+/// part2 = 23655822
+/// ```
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers(BTreeMap<(u8, Part), Answer>);
+
+impl Answers {
+    pub const DEFAULT_PATH: &'static str = "data/answers.toml";
+
+    pub fn load(path: &Path) -> Result<Self> {
+        Self::parse(&input::read_file(path)?)
+    }
+
+    pub fn parse(input: &Input) -> Result<Self> {
+        answers.parse(input.text.as_str()).map_err(|err| {
+            Error::Parse(ParseError::new(
+                &input.name,
+                &input.text,
+                err.offset(),
+                err.inner(),
+            ))
+        })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+}
+
+fn answers(input: &mut Stream<'_>) -> PResult<Answers> {
+    preceded(blank_lines, repeat_till(0.., section, end_of_input))
+        .map(|(sections, _): (Vec<_>, _)| {
+            Answers(
+                sections
+                    .into_iter()
+                    .flat_map(|(day, entries): (u8, Vec<_>)| {
+                        entries
+                            .into_iter()
+                            .map(move |(part, answer)| ((day, part), answer))
+                    })
+                    .collect(),
+            )
+        })
+        .parse_next(input)
+}
+
+fn section(input: &mut Stream<'_>) -> PResult<(u8, Vec<(Part, Answer)>)> {
+    (
+        terminated(
+            delimited(
+                '['.context(StrContext::Expected(StrContextValue::Description(
+                    "[dayNN] table",
+                ))),
+                cut_err(preceded("day", digit1.parse_to())),
+                cut_err(']'),
+            ),
+            cut_err(line_end),
+        )
+        .context(StrContext::Label("table header")),
+        repeat(0.., entry),
+    )
+        .parse_next(input)
+}
+
+fn entry(input: &mut Stream<'_>) -> PResult<(Part, Answer)> {
+    preceded(
+        (not('['), not(end_of_input)),
+        cut_err(terminated(
+            separated_pair(part, (space0, '=', space0), value),
+            line_end,
+        )),
+    )
+    .context(StrContext::Label("answer"))
+    .parse_next(input)
+}
+
+fn part(input: &mut Stream<'_>) -> PResult<Part> {
+    alt(("part1".value(Part::One), "part2".value(Part::Two)))
+        .context(StrContext::Expected(StrContextValue::StringLiteral(
+            "part2",
+        )))
+        .context(StrContext::Expected(StrContextValue::StringLiteral(
+            "part1",
+        )))
+        .parse_next(input)
+}
+
+fn value(input: &mut Stream<'_>) -> PResult<Answer> {
+    alt((
+        dec_uint.map(Answer::Number),
+        delimited('"', take_till(0.., ['"', '\n']), '"').map(Answer::from),
+    ))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "integer or string",
+    )))
+    .parse_next(input)
+}
+
+/// The rest of a line, an optional comment and any blank lines after it.
+fn line_end(input: &mut Stream<'_>) -> PResult<()> {
+    (space0, opt(comment), end_of_line, blank_lines)
+        .void()
+        .parse_next(input)
+}
+
+fn blank_lines(input: &mut Stream<'_>) -> PResult<()> {
+    repeat(0.., (space0, opt(comment), line_ending)).parse_next(input)
+}
+
+fn comment<'i>(input: &mut Stream<'i>) -> PResult<Stream<'i>> {
+    preceded('#', take_till(0.., ['\r', '\n'])).parse_next(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Answers> {
+        Answers::parse(&Input {
+            name: "answers.toml".to_string(),
+            text: text.to_string(),
+        })
+    }
+
+    #[test]
+    fn parse_answers() {
+        let answers = parse(
+            "\
+# Accepted answers
+[day01]
+part1 = 2196996
+part2 = 23655822 # second star
+
+[day6]
+part1 = \"EFGH\"
+",
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(1, Part::One), Some(&Answer::Number(2196996)));
+        assert_eq!(answers.get(1, Part::Two), Some(&Answer::Number(23655822)));
+        assert_eq!(answers.get(6, Part::One), Some(&Answer::from("EFGH")));
+        assert_eq!(answers.get(6, Part::Two), None);
+        assert_eq!(parse("").unwrap(), Answers::default());
+    }
+
+    #[test]
+    fn reject_malformed_answers() {
+        let Err(Error::Parse(err)) = parse("[day01]\npart1 = 1\npart3 = 2\n") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.message, "expected \"part1\" or \"part2\" in answer");
+
+        let Err(Error::Parse(err)) = parse("part1 = 1\n") else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
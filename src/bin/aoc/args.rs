@@ -0,0 +1,379 @@
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use winnow::ascii::dec_uint;
+use winnow::combinator::{alt, opt, preceded, separated};
+use winnow::prelude::*;
+
+use adventofcode_2024::answers::Answers;
+use adventofcode_2024::days;
+use adventofcode_2024::input::Source;
+use adventofcode_2024::solution::Part;
+use adventofcode_2024::solution::Puzzle;
+
+pub const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+  run <days> [options]   Run the selected days and print a summary table
+  verify [<days>] [options]
+                         Check the selected days, or all of them, against
+                         the recorded answers
+  bench <days> [options] Time parse, part 1 and part 2 separately
+  help                   Print this message
+
+Options:
+  -p, --part <1|2>       Only run the given part
+  -i, --input <path>     Read the input from <path>, or stdin for `-`; only
+                         valid for a single day [default: data/dayNN.txt]
+  --explain              Print how each selected day reaches its answers,
+                         for days that support it
+  --stream               Solve from the input as it is read instead of
+                         loading it whole, for days that support it (day 3)
+  -a, --answers <path>   Answers to verify against [default: data/answers.toml]
+  -n, --iterations <n>   Timed runs per phase when benchmarking [default: 100]
+  -w, --warmup <n>       Untimed runs before timing [default: 10]
+  --save <path>          Write benchmark results as tab separated values
+  --baseline <path>      Compare medians against results saved by `--save`
+
+<days> is `all`, a day (`6`), a range (`1..=6`, `1..7`) or a comma separated
+list of these (`1,3..=5`).";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Help,
+    Run(RunArgs),
+    Verify(VerifyArgs),
+    Bench(BenchArgs),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub days: DaySelection,
+    pub part: Option<Part>,
+    pub input: Source,
+    pub explain: bool,
+    pub stream: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct VerifyArgs {
+    pub days: DaySelection,
+    pub answers: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BenchArgs {
+    pub days: DaySelection,
+    pub part: Option<Part>,
+    pub input: Source,
+    pub iterations: usize,
+    pub warmup: usize,
+    pub save: Option<PathBuf>,
+    pub baseline: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DaySelection {
+    All,
+    Some(Vec<RangeInclusive<u8>>),
+}
+
+impl DaySelection {
+    pub fn contains(&self, day: u8) -> bool {
+        match self {
+            DaySelection::All => true,
+            DaySelection::Some(ranges) => ranges.iter().any(|r| r.contains(&day)),
+        }
+    }
+
+    /// The day if exactly one is selected.
+    pub fn single(&self) -> Option<u8> {
+        match self {
+            DaySelection::Some(ranges)
+                if ranges.len() == 1 && ranges[0].start() == ranges[0].end() =>
+            {
+                Some(*ranges[0].start())
+            }
+            _ => None,
+        }
+    }
+
+    /// Registered days matching the selection, in day order.
+    pub fn resolve(&self) -> Result<Vec<&'static dyn Puzzle>, String> {
+        if let Some(day) = self.explicit().find(|day| days::find(*day).is_none()) {
+            return Err(format!("day {day} is not registered"));
+        }
+        let selected: Vec<_> = days::DAYS
+            .iter()
+            .copied()
+            .filter(|day| self.contains(day.day()))
+            .collect();
+        if selected.is_empty() {
+            return Err("no registered days match the selection".to_string());
+        }
+        Ok(selected)
+    }
+
+    /// Days named on their own rather than as part of a range.
+    pub fn explicit(&self) -> impl Iterator<Item = u8> + '_ {
+        let ranges = match self {
+            DaySelection::All => &[][..],
+            DaySelection::Some(ranges) => &ranges[..],
+        };
+        ranges
+            .iter()
+            .filter(|r| r.start() == r.end())
+            .map(|r| *r.start())
+    }
+}
+
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        None | Some("help" | "-h" | "--help") => Ok(Command::Help),
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("verify") => parse_verify(args).map(Command::Verify),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some(other) => Err(format!("unknown command `{other}`")),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut part = None;
+    let mut input = Source::Default;
+    let mut explain = false;
+    let mut stream = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => {
+                let value = args.next().ok_or("`--part` expects a value")?;
+                part = Some(parse_part(&value)?);
+            }
+            "--explain" => explain = true,
+            "--stream" => stream = true,
+            "--input" | "-i" => {
+                let value = args.next().ok_or("`--input` expects a path")?;
+                input = Source::from_arg(&value);
+            }
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let days = days.ok_or("missing <days>")?;
+    if input != Source::Default && days.single().is_none() {
+        return Err("`--input` can only be used with a single day".to_string());
+    }
+    if stream && explain {
+        return Err("`--stream` cannot be used with `--explain`".to_string());
+    }
+
+    Ok(RunArgs {
+        days,
+        part,
+        input,
+        explain,
+        stream,
+    })
+}
+
+fn parse_verify(mut args: impl Iterator<Item = String>) -> Result<VerifyArgs, String> {
+    let mut days = None;
+    let mut answers = PathBuf::from(Answers::DEFAULT_PATH);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" | "-a" => {
+                answers = args.next().ok_or("`--answers` expects a path")?.into();
+            }
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    Ok(VerifyArgs {
+        days: days.unwrap_or(DaySelection::All),
+        answers,
+    })
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<BenchArgs, String> {
+    let mut days = None;
+    let mut part = None;
+    let mut input = Source::Default;
+    let mut iterations = 100;
+    let mut warmup = 10;
+    let mut save = None;
+    let mut baseline = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("`{arg}` expects a value"));
+        match arg.as_str() {
+            "--part" | "-p" => part = Some(parse_part(&value()?)?),
+            "--input" | "-i" => input = Source::from_arg(&value()?),
+            "--iterations" | "-n" => iterations = parse_count(&value()?)?,
+            "--warmup" | "-w" => warmup = value()?.parse().map_err(|_| "invalid `--warmup`")?,
+            "--save" => save = Some(value()?.into()),
+            "--baseline" => baseline = Some(value()?.into()),
+            _ if days.is_none() => days = Some(parse_days(&arg)?),
+            _ => return Err(format!("unexpected argument `{arg}`")),
+        }
+    }
+
+    let days = days.ok_or("missing <days>")?;
+    if input != Source::Default && days.single().is_none() {
+        return Err("`--input` can only be used with a single day".to_string());
+    }
+
+    Ok(BenchArgs {
+        days,
+        part,
+        input,
+        iterations,
+        warmup,
+        save,
+        baseline,
+    })
+}
+
+fn parse_count(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(format!(
+            "invalid count `{value}`, expected a positive number"
+        )),
+    }
+}
+
+fn parse_part(value: &str) -> Result<Part, String> {
+    match value {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("invalid part `{value}`, expected 1 or 2")),
+    }
+}
+
+fn parse_days(value: &str) -> Result<DaySelection, String> {
+    alt((
+        "all".value(DaySelection::All),
+        separated(1.., day_range, ',').map(DaySelection::Some),
+    ))
+    .parse(value)
+    .map_err(|_| format!("invalid day selection `{value}`"))
+}
+
+fn day_range(input: &mut &str) -> PResult<RangeInclusive<u8>> {
+    let start = dec_uint.parse_next(input)?;
+    let end = opt(alt((
+        preceded("..=", dec_uint),
+        preceded("..", dec_uint.verify(move |end: &u8| *end > start)).map(|end: u8| end - 1),
+    )))
+    .parse_next(input)?;
+    Ok(start..=end.unwrap_or(start))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(str::to_string)
+    }
+
+    #[rstest]
+    #[case("all", DaySelection::All)]
+    #[case("6", DaySelection::Some(vec![6..=6]))]
+    #[case("1..=6", DaySelection::Some(vec![1..=6]))]
+    #[case("1..7", DaySelection::Some(vec![1..=6]))]
+    #[case("1,3..=5", DaySelection::Some(vec![1..=1, 3..=5]))]
+    fn days(#[case] input: &str, #[case] expected: DaySelection) {
+        assert_eq!(parse_days(input), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("1..1")]
+    #[case("1..=")]
+    #[case("one")]
+    fn invalid_days(#[case] input: &str) {
+        assert!(parse_days(input).is_err(), "'{input}'");
+    }
+
+    #[test]
+    fn run_command() {
+        assert_eq!(
+            parse(args("run all --part 2")),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::All,
+                part: Some(Part::Two),
+                input: Source::Default,
+                explain: false,
+                stream: false,
+            }))
+        );
+        assert_eq!(
+            parse(args("run 5 -i - --explain")),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::Some(vec![5..=5]),
+                part: None,
+                input: Source::Stdin,
+                explain: true,
+                stream: false,
+            }))
+        );
+        assert_eq!(
+            parse(args("run 3 --stream -i -")),
+            Ok(Command::Run(RunArgs {
+                days: DaySelection::Some(vec![3..=3]),
+                part: None,
+                input: Source::Stdin,
+                explain: false,
+                stream: true,
+            }))
+        );
+        assert!(parse(args("run 3 --stream --explain")).is_err());
+        assert!(parse(args("run 1..=6 --input example.txt")).is_err());
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run 1 --part 3")).is_err());
+        assert_eq!(parse(args("")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn bench_command() {
+        assert_eq!(
+            parse(args("bench 1 -n 5 --warmup 0 -p 1 --save bench.tsv")),
+            Ok(Command::Bench(BenchArgs {
+                days: DaySelection::Some(vec![1..=1]),
+                part: Some(Part::One),
+                input: Source::Default,
+                iterations: 5,
+                warmup: 0,
+                save: Some(PathBuf::from("bench.tsv")),
+                baseline: None,
+            }))
+        );
+        assert!(parse(args("bench 1 -n 0")).is_err());
+        assert!(parse(args("bench 1 --baseline")).is_err());
+    }
+
+    #[test]
+    fn verify_command() {
+        assert_eq!(
+            parse(args("verify")),
+            Ok(Command::Verify(VerifyArgs {
+                days: DaySelection::All,
+                answers: PathBuf::from("data/answers.toml"),
+            }))
+        );
+        assert_eq!(
+            parse(args("verify 2 -a answers.toml")),
+            Ok(Command::Verify(VerifyArgs {
+                days: DaySelection::Some(vec![2..=2]),
+                answers: PathBuf::from("answers.toml"),
+            }))
+        );
+    }
+}
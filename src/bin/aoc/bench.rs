@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::fs;
+use std::hint::black_box;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use adventofcode_2024::error::Error;
+use adventofcode_2024::input;
+use adventofcode_2024::solution::{Parsed, Part, Puzzle};
+
+use crate::alloc::Usage;
+use crate::args::BenchArgs;
+
+/// Timing and allocation statistics for one phase of a day.
+#[derive(Debug, Clone, PartialEq)]
+struct Stats {
+    day: u8,
+    phase: String,
+    iterations: usize,
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+    p95: Duration,
+    allocations: u64,
+    bytes: u64,
+}
+
+impl Stats {
+    fn new(day: u8, phase: String, mut samples: Vec<Duration>, usage: Usage) -> Self {
+        samples.sort_unstable();
+        let n = samples.len();
+        Stats {
+            day,
+            phase,
+            iterations: n,
+            min: samples[0],
+            median: samples[n / 2],
+            mean: samples.iter().sum::<Duration>() / n as u32,
+            p95: samples[(n * 95).div_ceil(100) - 1],
+            allocations: usage.allocations / n as u64,
+            bytes: usage.bytes / n as u64,
+        }
+    }
+
+    /// One tab separated line, as written by `--save` and read by `--baseline`.
+    fn to_tsv(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.day,
+            self.phase,
+            self.iterations,
+            self.min.as_nanos(),
+            self.median.as_nanos(),
+            self.mean.as_nanos(),
+            self.p95.as_nanos(),
+            self.allocations,
+            self.bytes
+        )
+    }
+
+    fn from_tsv(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [day, phase, iterations, min, median, mean, p95, allocations, bytes] = fields[..]
+        else {
+            return None;
+        };
+        let nanos = |field: &str| field.parse().ok().map(Duration::from_nanos);
+        Some(Stats {
+            day: day.parse().ok()?,
+            phase: phase.to_string(),
+            iterations: iterations.parse().ok()?,
+            min: nanos(min)?,
+            median: nanos(median)?,
+            mean: nanos(mean)?,
+            p95: nanos(p95)?,
+            allocations: allocations.parse().ok()?,
+            bytes: bytes.parse().ok()?,
+        })
+    }
+}
+
+const TSV_HEADER: &str =
+    "# day\tphase\titerations\tmin_ns\tmedian_ns\tmean_ns\tp95_ns\tallocs\tbytes";
+
+pub fn bench(args: BenchArgs) -> ExitCode {
+    let selected = match args.days.resolve() {
+        Ok(selected) => selected,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::from(2);
+        }
+    };
+    let baseline = match args.baseline.as_deref().map(load_baseline).transpose() {
+        Ok(baseline) => baseline.unwrap_or_default(),
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut results = Vec::new();
+    for day in selected {
+        match bench_day(day, &args) {
+            Ok(stats) => results.extend(stats),
+            Err(err) => {
+                eprintln!("error: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    print_table(&results, &baseline);
+
+    if let Some(path) = &args.save {
+        let lines: Vec<String> = results.iter().map(Stats::to_tsv).collect();
+        let contents = format!("{TSV_HEADER}\n{}\n", lines.join("\n"));
+        if let Err(err) = fs::write(path, contents) {
+            eprintln!("error: cannot write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn bench_day(day: &dyn Puzzle, args: &BenchArgs) -> Result<Vec<Stats>, Error> {
+    let input = input::load(day.day(), &args.input)?;
+
+    let parse = measure(args.warmup, args.iterations, || {
+        black_box(day.parse(black_box(&input)))
+    });
+    let parsed = day.parse(&input)?;
+    let mut stats = vec![Stats::new(day.day(), "parse".to_string(), parse.0, parse.1)];
+
+    for part in Part::ALL {
+        if args.part.is_some_and(|p| p != part) {
+            continue;
+        }
+        if let Err(Error::Unimplemented) = parsed.solve(part) {
+            continue;
+        }
+        let (samples, usage) = measure(args.warmup, args.iterations, || {
+            black_box(solve(&*parsed, part))
+        });
+        stats.push(Stats::new(day.day(), format!("part{part}"), samples, usage));
+    }
+    Ok(stats)
+}
+
+fn solve(parsed: &dyn Parsed, part: Part) -> Result<(), Error> {
+    parsed.solve(black_box(part)).map(|answer| {
+        black_box(answer);
+    })
+}
+
+/// Runs `f` `warmup` times, then times `iterations` runs. Values returned by
+/// `f` are dropped outside of the timed region.
+fn measure<T>(
+    warmup: usize,
+    iterations: usize,
+    mut f: impl FnMut() -> T,
+) -> (Vec<Duration>, Usage) {
+    for _ in 0..warmup {
+        drop(f());
+    }
+
+    let mut samples = Vec::with_capacity(iterations);
+    let mut usage = Usage::default();
+    for _ in 0..iterations {
+        let allocated = Usage::now();
+        let start = Instant::now();
+        let value = f();
+        samples.push(start.elapsed());
+        let used = Usage::since(allocated);
+        usage.allocations += used.allocations;
+        usage.bytes += used.bytes;
+        drop(value);
+    }
+    (samples, usage)
+}
+
+fn load_baseline(path: &std::path::Path) -> Result<HashMap<(u8, String), Stats>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(idx, line)| {
+            Stats::from_tsv(line)
+                .map(|stats| ((stats.day, stats.phase.clone()), stats))
+                .ok_or_else(|| format!("{}:{}: malformed benchmark line", path.display(), idx + 1))
+        })
+        .collect()
+}
+
+fn print_table(results: &[Stats], baseline: &HashMap<(u8, String), Stats>) {
+    let compare = !baseline.is_empty();
+
+    print!(
+        "Day  Phase  Iters  {:>10}  {:>10}  {:>10}  {:>10}  {:>7}  {:>10}",
+        "Min", "Median", "Mean", "p95", "Allocs", "Bytes"
+    );
+    println!("{}", if compare { "  vs baseline" } else { "" });
+    for stats in results {
+        print!(
+            "{:>3}  {:<5}  {:>5}  {:>10}  {:>10}  {:>10}  {:>10}  {:>7}  {:>10}",
+            stats.day,
+            stats.phase,
+            stats.iterations,
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.mean),
+            format!("{:.2?}", stats.p95),
+            stats.allocations,
+            stats.bytes,
+        );
+        match baseline.get(&(stats.day, stats.phase.clone())) {
+            Some(base) => println!("  {:>+10.1}%", change(base.median, stats.median)),
+            None if compare => println!("  {:>11}", "-"),
+            None => println!(),
+        }
+    }
+}
+
+/// Percentage change of the median from `base` to `current`.
+fn change(base: Duration, current: Duration) -> f64 {
+    (current.as_secs_f64() / base.as_secs_f64() - 1.0) * 100.0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn summarise_samples() {
+        let samples = (1..=20).rev().map(Duration::from_micros).collect();
+        let usage = Usage {
+            allocations: 40,
+            bytes: 2000,
+        };
+
+        let stats = Stats::new(1, "parse".to_string(), samples, usage);
+
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(11));
+        assert_eq!(stats.mean, Duration::from_nanos(10_500));
+        assert_eq!(stats.p95, Duration::from_micros(19));
+        assert_eq!((stats.allocations, stats.bytes), (2, 100));
+        assert_eq!(Stats::from_tsv(&stats.to_tsv()), Some(stats));
+    }
+}
@@ -0,0 +1,186 @@
+use std::env;
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use adventofcode_2024::error::Error;
+use adventofcode_2024::input;
+use adventofcode_2024::solution::{Part, Puzzle};
+
+use crate::args::RunArgs;
+
+struct Row {
+    day: u8,
+    part: String,
+    answer: String,
+    elapsed: Option<Duration>,
+}
+
+pub fn run(args: RunArgs) -> ExitCode {
+    let selected = match args.days.resolve() {
+        Ok(selected) => selected,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut failed = false;
+    let mut rows = Vec::new();
+    for day in selected {
+        if args.stream {
+            failed |= !stream(day, &args, &mut rows);
+            continue;
+        }
+        let input = match input::load(day.day(), &args.input) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("error: {err}");
+                failed = true;
+                continue;
+            }
+        };
+
+        let start = Instant::now();
+        let parsed = day.parse(&input);
+        rows.push(Row {
+            day: day.day(),
+            part: "parse".to_string(),
+            answer: match &parsed {
+                Ok(_) => String::new(),
+                Err(_) => "error".to_string(),
+            },
+            elapsed: Some(start.elapsed()),
+        });
+        let parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                eprintln!("error: {err}\n");
+                failed = true;
+                continue;
+            }
+        };
+
+        if args.explain {
+            let colour = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+            match parsed.explain(&input.text, colour) {
+                Ok(explanation) => println!("{explanation}"),
+                Err(Error::Unimplemented) => {
+                    eprintln!("note: day {} has no explanation\n", day.day())
+                }
+                Err(err) => {
+                    eprintln!("error: {err}\n");
+                    failed = true;
+                }
+            }
+        }
+
+        for part in Part::ALL {
+            if args.part.is_some_and(|p| p != part) {
+                continue;
+            }
+            let start = Instant::now();
+            let answer = parsed.solve(part);
+            let elapsed = start.elapsed();
+            rows.push(match answer {
+                Ok(answer) => Row {
+                    day: day.day(),
+                    part: part.to_string(),
+                    answer: answer.to_string(),
+                    elapsed: Some(elapsed),
+                },
+                Err(Error::Unimplemented) => Row {
+                    day: day.day(),
+                    part: part.to_string(),
+                    answer: "-".to_string(),
+                    elapsed: None,
+                },
+                Err(err) => {
+                    failed = true;
+                    Row {
+                        day: day.day(),
+                        part: part.to_string(),
+                        answer: format!("error: {err}"),
+                        elapsed: None,
+                    }
+                }
+            });
+        }
+    }
+
+    if !rows.is_empty() {
+        print_table(&rows);
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Solves `day` from its input as it is read, adding a row for the pass and
+/// one per part. Returns whether it succeeded.
+fn stream(day: &dyn Puzzle, args: &RunArgs, rows: &mut Vec<Row>) -> bool {
+    let result = input::open(day.day(), &args.input).and_then(|mut input| {
+        let start = Instant::now();
+        let answers = day.stream(&mut input)?;
+        Ok((answers, start.elapsed()))
+    });
+    let (answers, elapsed) = match result {
+        Ok(result) => result,
+        Err(Error::Unimplemented) => {
+            eprintln!("error: day {} cannot stream its input\n", day.day());
+            return false;
+        }
+        Err(err) => {
+            eprintln!("error: {err}\n");
+            return false;
+        }
+    };
+
+    rows.push(Row {
+        day: day.day(),
+        part: "stream".to_string(),
+        answer: String::new(),
+        elapsed: Some(elapsed),
+    });
+    for (part, answer) in Part::ALL.into_iter().zip(answers) {
+        if args.part.is_none_or(|p| p == part) {
+            rows.push(Row {
+                day: day.day(),
+                part: part.to_string(),
+                answer: answer.to_string(),
+                elapsed: None,
+            });
+        }
+    }
+    true
+}
+
+fn print_table(rows: &[Row]) {
+    let width = rows
+        .iter()
+        .map(|row| row.answer.len())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or_default();
+    let rule = "-".repeat(24 + width);
+
+    println!("Day  Part   {:<width$}  {:>10}", "Answer", "Time");
+    println!("{rule}");
+    for row in rows {
+        let elapsed = row.elapsed.map(|e| format!("{e:.2?}")).unwrap_or_default();
+        println!(
+            "{:>3}  {:>5}  {:<width$}  {:>10}",
+            row.day, row.part, row.answer, elapsed
+        );
+    }
+    let total: Duration = rows.iter().filter_map(|row| row.elapsed).sum();
+    println!("{rule}");
+    println!(
+        "{:>10}  {:<width$}  {:>10}",
+        "",
+        "Total",
+        format!("{total:.2?}")
+    );
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+use std::process::ExitCode;
+
+use adventofcode_2024::answers::Answers;
+use adventofcode_2024::error::Error;
+use adventofcode_2024::input::{self, Source};
+use adventofcode_2024::solution::{Answer, Part};
+
+use crate::args::VerifyArgs;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Missing => "MISSING",
+        })
+    }
+}
+
+enum Outcome {
+    Solved(Answer),
+    Unimplemented,
+    Failed(String),
+}
+
+struct Row {
+    day: u8,
+    part: Part,
+    status: Status,
+    expected: String,
+    actual: String,
+}
+
+pub fn verify(args: VerifyArgs) -> ExitCode {
+    let selected = match args.days.resolve() {
+        Ok(selected) => selected,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::from(2);
+        }
+    };
+    let answers = match Answers::load(&args.answers) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut rows = Vec::new();
+    for day in selected {
+        let parsed = input::load(day.day(), &Source::Default).and_then(|input| day.parse(&input));
+        if let Err(err) = &parsed {
+            eprintln!("error: {err}\n");
+        }
+
+        for part in Part::ALL {
+            let outcome = match &parsed {
+                Ok(parsed) => match parsed.solve(part) {
+                    Ok(answer) => Outcome::Solved(answer),
+                    Err(Error::Unimplemented) => Outcome::Unimplemented,
+                    Err(err) => Outcome::Failed(format!("error: {err}")),
+                },
+                Err(Error::Io { .. }) => Outcome::Failed("no input".to_string()),
+                Err(_) => Outcome::Failed("parse error".to_string()),
+            };
+            rows.push(check(
+                day.day(),
+                part,
+                answers.get(day.day(), part),
+                outcome,
+            ));
+        }
+    }
+
+    print_table(&rows);
+
+    let count = |status| rows.iter().filter(|row| row.status == status).count();
+    let failed = count(Status::Fail);
+    println!(
+        "\n{} passed, {failed} failed, {} missing",
+        count(Status::Pass),
+        count(Status::Missing)
+    );
+
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn check(day: u8, part: Part, expected: Option<&Answer>, outcome: Outcome) -> Row {
+    let (status, actual) = match (expected, outcome) {
+        (Some(expected), Outcome::Solved(actual)) if *expected == actual => {
+            (Status::Pass, actual.to_string())
+        }
+        (None, Outcome::Solved(actual)) => (Status::Missing, actual.to_string()),
+        (None, Outcome::Unimplemented) => (Status::Missing, "not implemented".to_string()),
+        (Some(_), Outcome::Solved(actual)) => (Status::Fail, actual.to_string()),
+        (Some(_), Outcome::Unimplemented) => (Status::Fail, "not implemented".to_string()),
+        (_, Outcome::Failed(reason)) => (Status::Fail, reason),
+    };
+
+    Row {
+        day,
+        part,
+        status,
+        expected: expected.map_or_else(|| "-".to_string(), Answer::to_string),
+        actual,
+    }
+}
+
+fn print_table(rows: &[Row]) {
+    let width = rows
+        .iter()
+        .map(|row| row.expected.len())
+        .chain(["Expected".len()])
+        .max()
+        .unwrap_or_default();
+
+    println!("Day  Part  Status   {:<width$}  Actual", "Expected");
+    for row in rows {
+        println!(
+            "{:>3}  {:>4}  {:<7}  {:<width$}  {}",
+            row.day, row.part, row.status, row.expected, row.actual
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use crate::error::{Error, Result};
+use crate::prelude::*;
+use crate::solution::{Answer, Solution};
+
+use std::collections::HashMap;
+
+use winnow::ascii::space1;
+use winnow::error::{StrContext, StrContextValue};
+use winnow::prelude::*;
+
+pub struct Day01;
+
+pub struct Lists {
+    left: Vec<u64>,
+    right: Vec<u64>,
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Lists;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Lists> {
+        lines_of(location_pair)
+            .map(|pairs| {
+                let (left, right) = pairs.into_iter().unzip();
+                Lists { left, right }
+            })
+            .parse_next(input)
+    }
+
+    fn part1(lists: &Lists) -> Result<Answer> {
+        let mut left = lists.left.clone();
+        let mut right = lists.right.clone();
+        left.sort_unstable();
+        right.sort_unstable();
+
+        let total = left
+            .into_iter()
+            .zip(right)
+            .try_fold(0u64, |total, (n1, n2)| {
+                total
+                    .checked_add(n1.abs_diff(n2))
+                    .ok_or_else(|| Error::Overflow(format!("total distance at pair {n1}, {n2}")))
+            })?;
+
+        Ok(total.into())
+    }
+
+    fn part2(lists: &Lists) -> Result<Answer> {
+        let right = lists
+            .right
+            .iter()
+            .fold(HashMap::<u64, u64>::new(), |mut counts, n| {
+                counts.entry(*n).and_modify(|c| *c += 1).or_insert(1);
+                counts
+            });
+
+        let total = lists
+            .left
+            .iter()
+            .enumerate()
+            .try_fold(0u64, |total, (line, n)| {
+                right
+                    .get(n)
+                    .map_or(Some(0), |count| count.checked_mul(*n))
+                    .and_then(|score| total.checked_add(score))
+                    .ok_or_else(|| {
+                        Error::Overflow(format!("similarity score at line {}", line + 1))
+                    })
+            })?;
+
+        Ok(total.into())
+    }
+}
+
+fn location_pair(input: &mut Stream<'_>) -> PResult<(u64, u64)> {
+    pair_sep(
+        location_id,
+        space1.context(StrContext::Expected(StrContextValue::Description(
+            "whitespace",
+        ))),
+    )
+    .context(StrContext::Label("location pair"))
+    .parse_next(input)
+}
+
+fn location_id(input: &mut Stream<'_>) -> PResult<u64> {
+    number
+        .context(StrContext::Expected(StrContextValue::Description(
+            "location id",
+        )))
+        .parse_next(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_lists() {
+        let input = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+        let lists = Day01::parse.parse(input).unwrap();
+
+        assert_eq!(Day01::part1(&lists).unwrap(), Answer::Number(11));
+        assert_eq!(Day01::part2(&lists).unwrap(), Answer::Number(31));
+    }
+
+    #[test]
+    fn overflowing_totals() {
+        let input = "\
+18446744073709551615   0
+18446744073709551615   0
+18446744073709551615   18446744073709551615
+";
+        let lists = Day01::parse.parse(input).unwrap();
+
+        assert_eq!(
+            Day01::part1(&lists).unwrap_err().to_string(),
+            "arithmetic overflow: total distance at pair 18446744073709551615, 0"
+        );
+        assert_eq!(
+            Day01::part2(&lists).unwrap_err().to_string(),
+            "arithmetic overflow: similarity score at line 2"
+        );
+        assert!(Day01::parse.parse("18446744073709551616   0\n").is_err());
+    }
+}
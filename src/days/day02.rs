@@ -0,0 +1,388 @@
+use crate::error::Result;
+use crate::prelude::*;
+use crate::solution::{Answer, Solution};
+
+use std::fmt::{self, Write};
+
+use winnow::error::StrContext;
+use winnow::prelude::*;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Vec<Vec<u32>>> {
+        lines_of(number_line)
+            .context(StrContext::Label("report"))
+            .parse_next(input)
+    }
+
+    fn part1(reports: &Vec<Vec<u32>>) -> Result<Answer> {
+        Ok(reports
+            .iter()
+            .filter(|levels| is_safe(levels))
+            .count()
+            .into())
+    }
+
+    fn part2(reports: &Vec<Vec<u32>>) -> Result<Answer> {
+        Ok(reports
+            .iter()
+            .filter(|levels| is_safe_dampened(levels))
+            .count()
+            .into())
+    }
+
+    fn explain(reports: &Vec<Vec<u32>>, _text: &str, colour: bool) -> Result<String> {
+        Ok(explain(reports, colour))
+    }
+}
+
+/// The first thing that makes a report unsafe, by the index of the level at
+/// which it is noticed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The level equals the one before it.
+    Duplicate { index: usize },
+    /// The level goes the other way to the first two.
+    DirectionChange { index: usize },
+    /// The level differs from the one before it by more than three.
+    Step { index: usize, size: u32 },
+}
+
+/// Whether a report is safe, and if not why, or which level the Problem
+/// Dampener removes to make it safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diagnosis {
+    Safe,
+    /// Safe once the level at `index` is removed, the first one that works.
+    SafeAfterRemoving {
+        index: usize,
+        value: u32,
+    },
+    /// Unsafe even with the dampener; `reason` is the fault in the full report.
+    Unsafe {
+        reason: Fault,
+    },
+}
+
+impl Diagnosis {
+    /// Whether the report counts as safe with the dampener.
+    pub fn is_safe(self) -> bool {
+        !matches!(self, Diagnosis::Unsafe { .. })
+    }
+}
+
+/// Checks a report from scratch by trying each level removal in turn, unlike
+/// [`is_safe_dampened`] which makes one pass.
+pub fn diagnose(levels: &[u32]) -> Diagnosis {
+    let Some(reason) = fault(levels.iter().copied()) else {
+        return Diagnosis::Safe;
+    };
+    (0..levels.len())
+        .find(|&index| {
+            let rest = levels[..index].iter().chain(&levels[index + 1..]);
+            fault(rest.copied()).is_none()
+        })
+        .map_or(Diagnosis::Unsafe { reason }, |index| {
+            Diagnosis::SafeAfterRemoving {
+                index,
+                value: levels[index],
+            }
+        })
+}
+
+fn fault(levels: impl Iterator<Item = u32>) -> Option<Fault> {
+    let mut increasing = None;
+    levels
+        .map_windows(|[a, b]| (*a, *b))
+        .enumerate()
+        .find_map(|(i, (a, b))| {
+            let index = i + 1;
+            if a == b {
+                return Some(Fault::Duplicate { index });
+            }
+            if *increasing.get_or_insert(b > a) != (b > a) {
+                return Some(Fault::DirectionChange { index });
+            }
+            let size = a.abs_diff(b);
+            (size > 3).then_some(Fault::Step { index, size })
+        })
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::Duplicate { index } => write!(f, "duplicate level at index {index}"),
+            Fault::DirectionChange { index } => write!(f, "direction changes at index {index}"),
+            Fault::Step { index, size } => write!(f, "step of {size} at index {index}"),
+        }
+    }
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnosis::Safe => f.write_str("safe"),
+            Diagnosis::SafeAfterRemoving { index, value } => {
+                write!(f, "safe after removing {value} at index {index}")
+            }
+            Diagnosis::Unsafe { reason } => write!(f, "unsafe: {reason}"),
+        }
+    }
+}
+
+/// Every report with its line number and [`Diagnosis`], then a count of each
+/// verdict.
+fn explain(reports: &[Vec<u32>], colour: bool) -> String {
+    let diagnoses: Vec<_> = reports.iter().map(|levels| diagnose(levels)).collect();
+    let levels: Vec<_> = reports
+        .iter()
+        .map(|levels| {
+            levels
+                .iter()
+                .map(u32::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect();
+    let line_width = reports.len().to_string().len();
+    let levels_width = levels.iter().map(String::len).max().unwrap_or_default();
+
+    let mut out = String::new();
+    for (line, (levels, diagnosis)) in levels.iter().zip(&diagnoses).enumerate() {
+        let style = match diagnosis {
+            Diagnosis::Safe => "\x1b[32m",
+            Diagnosis::SafeAfterRemoving { .. } => "\x1b[33m",
+            Diagnosis::Unsafe { .. } => "\x1b[31m",
+        };
+        let (style, reset) = if colour { (style, "\x1b[0m") } else { ("", "") };
+        writeln!(
+            out,
+            "{:>line_width$}  {levels:<levels_width$}  {style}{diagnosis}{reset}",
+            line + 1
+        )
+        .unwrap();
+    }
+
+    let count = |verdict: fn(&Diagnosis) -> bool| diagnoses.iter().filter(|d| verdict(d)).count();
+    write!(
+        out,
+        "\n{} safe, {} safe after removing a level, {} unsafe",
+        count(|d| matches!(d, Diagnosis::Safe)),
+        count(|d| matches!(d, Diagnosis::SafeAfterRemoving { .. })),
+        count(|d| matches!(d, Diagnosis::Unsafe { .. })),
+    )
+    .unwrap();
+    out
+}
+
+fn is_safe(levels: &[u32]) -> bool {
+    levels
+        .iter()
+        .map_windows(|[a, b]| i64::from(**a) - i64::from(**b))
+        .try_fold(0, |last, delta| {
+            if (last == 0 || same_gradient(last, delta)) && within_range(delta) {
+                Some(delta)
+            } else {
+                None
+            }
+        })
+        .is_some()
+}
+
+#[derive(Debug)]
+enum LastDelta {
+    Start,
+    Valid(i64),
+    Invalid((i64, Option<i64>)),
+}
+
+fn is_safe_dampened(levels: &[u32]) -> bool {
+    levels
+        .iter()
+        .map_windows(|[a, b]| i64::from(**b) - i64::from(**a))
+        .try_fold(
+            (LastDelta::Start, -1),
+            |(last, retries), delta| match last {
+                LastDelta::Start => {
+                    if within_range(delta) {
+                        Some((LastDelta::Valid(delta), 1))
+                    } else {
+                        Some((LastDelta::Invalid((delta, None)), 0))
+                    }
+                }
+                LastDelta::Invalid((first, None)) => {
+                    Some((LastDelta::Invalid((delta, Some(first))), retries))
+                }
+                LastDelta::Invalid((p1, Some(p2))) => {
+                    if within_range(delta)
+                        && (same_gradient(p1, delta) && within_range(p1)
+                            || (same_gradient(p2 + p1, delta) && within_range(p2 + p1)))
+                    {
+                        Some((LastDelta::Valid(delta), retries))
+                    } else if same_gradient(p1 + delta, p2)
+                        && within_range(p1 + delta)
+                        && within_range(p2)
+                    {
+                        Some((LastDelta::Valid(p1 + delta), retries))
+                    } else {
+                        None
+                    }
+                }
+                LastDelta::Valid(first) => {
+                    if same_gradient(first, delta) && within_range(delta) {
+                        Some((LastDelta::Valid(delta), retries))
+                    } else if retries > 0 {
+                        Some((LastDelta::Invalid((delta, Some(first))), 0))
+                    } else {
+                        None
+                    }
+                }
+            },
+        )
+        .is_some()
+}
+
+#[inline]
+fn within_range(n: i64) -> bool {
+    (1..=3).contains(&n.abs())
+}
+
+#[inline]
+fn same_gradient(a: i64, b: i64) -> bool {
+    a ^ b >= 0
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ParseError;
+    use rstest::rstest;
+    use winnow::error::ContextError;
+
+    #[test]
+    fn test_line() {
+        let input = "7 6 4 2 1";
+        let result = number_line::<u32, ContextError>.parse(input).unwrap();
+        assert_eq!(result, vec![7, 6, 4, 2, 1]);
+    }
+
+    #[rstest]
+    #[case("7 6 4 2 1\n1 2 x 4\n1 3 6 7 9\n", 2, 4)]
+    #[case("7 6 4 2 1\n\n1 3 6 7 9\n", 2, 1)]
+    #[case("7 6 4 2 1\n1 3 6 7 9!", 2, 10)]
+    fn malformed(#[case] input: &str, #[case] line: usize, #[case] column: usize) {
+        let err = Day02::parse.parse(input).unwrap_err();
+        let err = ParseError::new("input", input, err.offset(), err.inner());
+        assert_eq!((err.line, err.column), (line, column), "{err}");
+    }
+
+    #[rstest]
+    #[case("7 6 4 2 1", 1)]
+    #[case("1 2 7 8 9", 0)]
+    #[case("9 7 6 2 1", 0)]
+    #[case("1 3 2 4 5", 0)]
+    #[case("8 6 4 4 1", 0)]
+    #[case("1 3 6 7 9", 1)]
+    #[case("10 16 17 20 23", 0)]
+    #[case("2147483647 2147483648 2147483649", 1)]
+    #[case("4294967295 0 4294967295", 0)]
+    fn part1(#[case] input: &str, #[case] count: usize) {
+        let reports = Day02::parse.parse(input).unwrap();
+        assert_eq!(
+            Day02::part1(&reports).unwrap(),
+            Answer::from(count),
+            "'{input}'"
+        );
+    }
+
+    #[rstest]
+    #[case("1 4 3 2 1", 1)]
+    #[case("1 6 7 8 9", 1)]
+    #[case("1 1 2 3 4 5", 1)]
+    #[case("1 2 3 4 5 5", 1)]
+    #[case("5 1 2 3 4 5", 1)]
+    #[case("7 10 8 10 11", 1)]
+    #[case("29 28 27 26 25 22 20", 1)]
+    #[case("48 46 47 49 51 54 56", 1)]
+    #[case("29 31 34 40 42 45 47 48", 0)]
+    #[case("34 37 38 40 42 46 43", 1)]
+    #[case("85 89 86 87 89", 1)]
+    #[case("10 12 12 9 7 4 2", 0)]
+    #[case("59 61 59 61 63", 0)]
+    #[case("72 75 73 73 73", 0)]
+    #[case("95 95 93 95 98", 0)]
+    #[case("61 66 70 71 71", 0)]
+    #[case("30 24 22 15 12", 0)]
+    #[case("68 67 69 66 65", 1)]
+    #[case("43 40 44 46 47 48 50", 1)]
+    #[case("51 46 46 44 41", 0)]
+    #[case("23 19 15 14 11 10", 0)]
+    #[case("56 57 58 58 64", 0)]
+    #[case("30 24 22 15 12", 0)]
+    #[case("38 37 34 35 28", 0)]
+    #[case("10 16 17 20 23", 1)]
+    #[case("70 70 73 79 80", 0)]
+    #[case("36 29 26 29 30", 0)]
+    #[case("69 70 69 66 63 60 58", 1)]
+    #[case("14 12 9 6 4 3 5", 1)]
+    #[case("26 33 30 32 34 36 39", 0)]
+    #[case("43 40 41 44 45 46 48 51", 1)]
+    #[case("85 89 86 87 89", 1)]
+    #[case("2147483647 2147483648 2147483649", 1)]
+    #[case("4294967295 0 4294967294 4294967293", 1)]
+    fn valid_part2(#[case] input: &str, #[case] count: usize) {
+        let reports = Day02::parse.parse(input).unwrap();
+        assert_eq!(
+            Day02::part2(&reports).unwrap(),
+            Answer::from(count),
+            "'{input}'"
+        );
+        assert_eq!(
+            diagnose(&reports[0]).is_safe(),
+            count == 1,
+            "'{input}': {}",
+            diagnose(&reports[0])
+        );
+    }
+
+    #[rstest]
+    #[case("7 6 4 2 1", Diagnosis::Safe)]
+    #[case("1 2 7 8 9", Diagnosis::Unsafe { reason: Fault::Step { index: 2, size: 5 } })]
+    #[case("9 7 6 2 1", Diagnosis::Unsafe { reason: Fault::Step { index: 3, size: 4 } })]
+    #[case("1 3 2 4 5", Diagnosis::SafeAfterRemoving { index: 1, value: 3 })]
+    #[case("8 6 4 4 1", Diagnosis::SafeAfterRemoving { index: 2, value: 4 })]
+    #[case("1 3 6 7 9", Diagnosis::Safe)]
+    #[case("5 1 2 3 4 5", Diagnosis::SafeAfterRemoving { index: 0, value: 5 })]
+    #[case("1 2 3 4 5 5", Diagnosis::SafeAfterRemoving { index: 4, value: 5 })]
+    #[case("72 75 73 73 73", Diagnosis::Unsafe { reason: Fault::DirectionChange { index: 2 } })]
+    #[case("95 95 93 95 98", Diagnosis::Unsafe { reason: Fault::Duplicate { index: 1 } })]
+    #[case("5", Diagnosis::Safe)]
+    fn diagnoses(#[case] input: &str, #[case] expected: Diagnosis) {
+        let reports = Day02::parse.parse(input).unwrap();
+        assert_eq!(diagnose(&reports[0]), expected, "'{input}'");
+    }
+
+    #[test]
+    fn explain_reports() {
+        let input = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+        let reports = Day02::parse.parse(input).unwrap();
+
+        assert_eq!(
+            explain(&reports, false),
+            "\
+1  7 6 4 2 1  safe
+2  1 2 7 8 9  unsafe: step of 5 at index 2
+3  9 7 6 2 1  unsafe: step of 4 at index 3
+4  1 3 2 4 5  safe after removing 3 at index 1
+5  8 6 4 4 1  safe after removing 4 at index 2
+6  1 3 6 7 9  safe
+
+2 safe, 2 safe after removing a level, 2 unsafe"
+        );
+    }
+}
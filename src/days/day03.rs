@@ -0,0 +1,675 @@
+use crate::error::{Error, ParseError, Result};
+use crate::prelude::*;
+use crate::solution::{Answer, Solution};
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::io::{self, Read};
+use std::ops::Range;
+
+use winnow::combinator::{alt, cut_err, delimited, opt};
+use winnow::error::{ContextError, ErrMode, StrContext, StrContextValue};
+use winnow::prelude::*;
+use winnow::stream::AsChar;
+use winnow::token::{any, take_while};
+
+pub struct Day03;
+
+/// An instruction found in the corrupted memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+/// An instruction and the bytes of the memory it was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub instruction: Instruction,
+    pub span: Range<usize>,
+}
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<Token>;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Vec<Token>> {
+        tokenize(input)
+    }
+
+    fn part1(tokens: &Vec<Token>) -> Result<Answer> {
+        let mut machine = Machine::new(Config {
+            conditionals: false,
+        });
+        Ok(machine.run(tokens)?.into())
+    }
+
+    fn part2(tokens: &Vec<Token>) -> Result<Answer> {
+        let mut machine = Machine::new(Config { conditionals: true });
+        Ok(machine.run(tokens)?.into())
+    }
+
+    fn explain(tokens: &Vec<Token>, text: &str, colour: bool) -> Result<String> {
+        explain(tokens, text, colour)
+    }
+
+    fn stream(name: &str, reader: &mut dyn Read) -> Result<[Answer; 2]> {
+        let mut part1 = Machine::new(Config {
+            conditionals: false,
+        });
+        let mut part2 = Machine::new(Config { conditionals: true });
+        for token in Scanner::new(reader) {
+            let token = token.map_err(|source| Error::Io {
+                name: name.to_string(),
+                source,
+            })?;
+            part1.execute(&token)?;
+            part2.execute(&token)?;
+        }
+        Ok([part1.total.into(), part2.total.into()])
+    }
+}
+
+/// Scans the whole input for instructions, skipping anything else.
+pub fn tokenize(input: &mut Stream<'_>) -> PResult<Vec<Token>> {
+    let len = input.len();
+    let mut tokens = Vec::new();
+    while !input.is_empty() {
+        let start = len - input.len();
+        match opt(instruction).parse_next(input)? {
+            Some(instruction) => tokens.push(Token {
+                instruction,
+                span: start..len - input.len(),
+            }),
+            None => {
+                any.parse_next(input)?;
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn instruction(input: &mut Stream<'_>) -> PResult<Instruction> {
+    alt((
+        delimited("mul(", pair_sep(operand, ','), ")").map(|(a, b)| Instruction::Mul(a, b)),
+        "do()".value(Instruction::Do),
+        "don't()".value(Instruction::Dont),
+    ))
+    .parse_next(input)
+}
+
+/// A `mul` operand: a number of one to three digits.
+fn operand(input: &mut Stream<'_>) -> PResult<u32> {
+    take_while(1..=3, AsChar::is_dec_digit)
+        .parse_to()
+        .parse_next(input)
+}
+
+/// Which instructions a [`Machine`] acts on.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    /// Whether `do()` and `don't()` switch `mul` on and off, rather than
+    /// being ignored.
+    pub conditionals: bool,
+}
+
+/// Executes instructions one at a time, summing the products of every `mul`
+/// while enabled.
+#[derive(Debug, Clone)]
+pub struct Machine {
+    config: Config,
+    pub enabled: bool,
+    pub total: u64,
+}
+
+impl Machine {
+    pub fn new(config: Config) -> Self {
+        Machine {
+            config,
+            enabled: true,
+            total: 0,
+        }
+    }
+
+    /// Executes one instruction and returns the new total, or `None` if the
+    /// total would overflow, leaving the machine unchanged.
+    pub fn step(&mut self, instruction: Instruction) -> Option<u64> {
+        match instruction {
+            Instruction::Mul(a, b) => {
+                if self.enabled {
+                    self.total = u64::from(a)
+                        .checked_mul(u64::from(b))
+                        .and_then(|product| self.total.checked_add(product))?;
+                }
+            }
+            Instruction::Do | Instruction::Dont if !self.config.conditionals => {}
+            Instruction::Do => self.enabled = true,
+            Instruction::Dont => self.enabled = false,
+        }
+        Some(self.total)
+    }
+
+    /// Executes one token, reporting where it came from if the total overflows.
+    pub fn execute(&mut self, token: &Token) -> Result<u64> {
+        self.step(token.instruction).ok_or_else(|| {
+            let Instruction::Mul(a, b) = token.instruction else {
+                unreachable!("only mul changes the total")
+            };
+            Error::Overflow(format!("mul({a},{b}) at offset {}", token.span.start))
+        })
+    }
+
+    /// Executes every token in order and returns the total.
+    pub fn run(&mut self, tokens: &[Token]) -> Result<u64> {
+        for token in tokens {
+            self.execute(token)?;
+        }
+        Ok(self.total)
+    }
+}
+
+/// The longest possible instruction, `mul(999,999)`.
+const LONGEST: usize = 12;
+
+/// Finds the same tokens as [`tokenize`] in memory read a chunk at a time.
+///
+/// An instruction is only parsed once the [`LONGEST`] bytes it could span have
+/// been read, so one split across a chunk boundary is found as if the memory
+/// were read whole. Spans are byte offsets from the start of the reader. The
+/// memory need not be valid UTF-8, as instructions are plain ASCII.
+pub struct Scanner<R> {
+    reader: R,
+    /// A chunk plus room for the unscanned tail of the previous one.
+    buf: Box<[u8]>,
+    /// Offset of `buf[0]` in the reader.
+    offset: usize,
+    /// Where in `buf` to look for the next instruction.
+    pos: usize,
+    /// End of the bytes read into `buf`.
+    end: usize,
+    eof: bool,
+}
+
+impl<R: Read> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner::with_chunk_size(reader, 64 * 1024)
+    }
+
+    pub fn with_chunk_size(reader: R, chunk_size: usize) -> Self {
+        Scanner {
+            reader,
+            buf: vec![0; chunk_size.max(1) + LONGEST].into(),
+            offset: 0,
+            pos: 0,
+            end: 0,
+            eof: false,
+        }
+    }
+
+    /// Moves what is left to scan to the front of the buffer and reads up to a
+    /// chunk after it, noting when the reader is exhausted.
+    fn fill(&mut self) -> io::Result<()> {
+        self.buf.copy_within(self.pos..self.end, 0);
+        self.offset += self.pos;
+        self.end -= self.pos;
+        self.pos = 0;
+
+        let read = loop {
+            match self.reader.read(&mut self.buf[self.end..]) {
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result?,
+            }
+        };
+        self.end += read;
+        self.eof = read == 0;
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Scanner<R> {
+    type Item = io::Result<Token>;
+
+    fn next(&mut self) -> Option<io::Result<Token>> {
+        loop {
+            while self.pos < self.end && (self.eof || self.end - self.pos >= LONGEST) {
+                let window = &self.buf[self.pos..self.end.min(self.pos + LONGEST)];
+                let text = match std::str::from_utf8(window) {
+                    Ok(text) => text,
+                    Err(err) => std::str::from_utf8(&window[..err.valid_up_to()]).unwrap(),
+                };
+
+                let mut rest = text;
+                if let Ok(instruction) = instruction.parse_next(&mut rest) {
+                    let start = self.offset + self.pos;
+                    self.pos += text.len() - rest.len();
+                    return Some(Ok(Token {
+                        instruction,
+                        span: start..self.offset + self.pos,
+                    }));
+                }
+                self.pos += 1;
+            }
+            if self.eof {
+                return None;
+            }
+            if let Err(err) = self.fill() {
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const ENABLED: &str = "\x1b[1;32m";
+const DISABLED: &str = "\x1b[31m";
+const TOGGLE: &str = "\x1b[36m";
+const NEAR_MISS: &str = "\x1b[4;33m";
+
+/// Something that starts like `mul` but is not an instruction.
+#[derive(Debug, PartialEq, Eq)]
+pub struct NearMiss {
+    /// From the start of the attempt up to and including the character that
+    /// made it fail, if there is one on the same line.
+    pub span: Range<usize>,
+    pub reason: String,
+}
+
+/// Every `mul` in `text` that does not start one of `tokens`.
+pub fn near_misses(tokens: &[Token], text: &str) -> Vec<NearMiss> {
+    let starts: HashSet<_> = tokens.iter().map(|token| token.span.start).collect();
+    text.match_indices("mul")
+        .map(|(start, _)| start)
+        .filter(|start| !starts.contains(start))
+        .filter_map(|start| {
+            let mut rest = &text[start..];
+            let err = match mul_attempt(&mut rest) {
+                Ok(()) => return None,
+                Err(ErrMode::Backtrack(err) | ErrMode::Cut(err)) => err,
+                Err(ErrMode::Incomplete(_)) => ContextError::new(),
+            };
+            let failed_at = text.len() - rest.len();
+            let end = rest
+                .chars()
+                .next()
+                .filter(|c| *c != '\n')
+                .map_or(failed_at, |c| failed_at + c.len_utf8());
+            Some(NearMiss {
+                span: start..end,
+                reason: ParseError::new("memory", text, failed_at, &err).message,
+            })
+        })
+        .collect()
+}
+
+/// Parses `mul(a,b)` committing to each piece, so the error says which one
+/// is missing.
+fn mul_attempt(input: &mut Stream<'_>) -> PResult<()> {
+    let number = || {
+        cut_err(operand).context(StrContext::Expected(StrContextValue::Description(
+            "number of 1 to 3 digits",
+        )))
+    };
+    let literal = |c: char| cut_err(c).context(StrContext::Expected(c.into()));
+
+    (
+        "mul",
+        literal('('),
+        number(),
+        literal(','),
+        number(),
+        literal(')'),
+    )
+        .void()
+        .parse_next(input)
+}
+
+/// `text` with the instructions highlighted, then a listing of every
+/// instruction with its offset and the running total of each part, then the
+/// near misses.
+///
+/// Without `colour`, counted `mul`s and `do`/`don't` are marked `[...]` and
+/// `mul`s disabled in part 2 `{...}`.
+fn explain(tokens: &[Token], text: &str, colour: bool) -> Result<String> {
+    let misses = near_misses(tokens, text);
+    let mut part1 = Machine::new(Config {
+        conditionals: false,
+    });
+    let mut part2 = Machine::new(Config { conditionals: true });
+
+    let mut memory = String::new();
+    let mut listing = Vec::new();
+    let mut last = 0;
+    for token in tokens {
+        let source = &text[token.span.clone()];
+        let (status, style, marks) = match token.instruction {
+            Instruction::Mul(..) if part2.enabled => ("counted", ENABLED, ('[', ']')),
+            Instruction::Mul(..) => ("disabled", DISABLED, ('{', '}')),
+            Instruction::Do => ("enable", TOGGLE, ('[', ']')),
+            Instruction::Dont => ("disable", TOGGLE, ('[', ']')),
+        };
+        highlight_misses(&mut memory, text, last..token.span.start, &misses, colour);
+        if colour {
+            write!(memory, "{style}{source}{RESET}").unwrap();
+        } else {
+            write!(memory, "{}{source}{}", marks.0, marks.1).unwrap();
+        }
+        last = token.span.end;
+
+        part1.execute(token)?;
+        part2.execute(token)?;
+        let product = match token.instruction {
+            Instruction::Mul(a, b) => (a * b).to_string(),
+            _ => String::new(),
+        };
+        listing.push([
+            token.span.start.to_string(),
+            source.to_string(),
+            status.to_string(),
+            product,
+            part1.total.to_string(),
+            part2.total.to_string(),
+        ]);
+    }
+    highlight_misses(&mut memory, text, last..text.len(), &misses, colour);
+
+    let mut out = memory.trim_end().to_string();
+    out.push_str("\n\n");
+    let header = [
+        "Offset",
+        "Instruction",
+        "Status",
+        "Product",
+        "Part 1",
+        "Part 2",
+    ]
+    .map(String::from);
+    let widths: Vec<_> = (0..header.len())
+        .map(|col| {
+            listing
+                .iter()
+                .chain([&header])
+                .map(|row| row[col].len())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    for row in [&header].into_iter().chain(&listing) {
+        let [offset, instruction, status, product, part1, part2] = row;
+        writeln!(
+            out,
+            "{offset:>w0$}  {instruction:<w1$}  {status:<w2$}  {product:>w3$}  {part1:>w4$}  {part2:>w5$}",
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+            w5 = widths[5],
+        )
+        .unwrap();
+    }
+
+    if !misses.is_empty() {
+        out.push_str("\nNear misses:\n");
+        let width = misses
+            .iter()
+            .map(|miss| miss.span.start.to_string().len())
+            .max()
+            .unwrap_or_default();
+        for miss in &misses {
+            writeln!(
+                out,
+                "{:>width$}  {:<12}  {}",
+                miss.span.start,
+                &text[miss.span.clone()],
+                miss.reason
+            )
+            .unwrap();
+        }
+    }
+    Ok(out)
+}
+
+/// Copies `text[range]` to `out`, underlining any near misses in it. Where
+/// near misses overlap, the later one is underlined from where the earlier
+/// one ends.
+fn highlight_misses(
+    out: &mut String,
+    text: &str,
+    range: Range<usize>,
+    misses: &[NearMiss],
+    colour: bool,
+) {
+    let mut at = range.start;
+    if colour {
+        for miss in misses
+            .iter()
+            .filter(|miss| range.contains(&miss.span.start))
+        {
+            let start = miss.span.start.max(at);
+            let end = miss.span.end.min(range.end);
+            if start >= end {
+                continue;
+            }
+            write!(
+                out,
+                "{}{NEAR_MISS}{}{RESET}",
+                &text[at..start],
+                &text[start..end]
+            )
+            .unwrap();
+            at = end;
+        }
+    }
+    out.push_str(&text[at..range.end]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn testing_parser() {
+        let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        let memory = Day03::parse.parse(input).unwrap();
+
+        assert_eq!(Day03::part1(&memory).unwrap(), Answer::Number(161));
+    }
+
+    #[test]
+    fn toggled_instructions() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let memory = Day03::parse.parse(input).unwrap();
+
+        assert_eq!(Day03::part2(&memory).unwrap(), Answer::Number(48));
+    }
+
+    #[test]
+    fn token_spans() {
+        let input = "xmul(2,4)&don't()_mul(5,5)+do()";
+        let tokens = Day03::parse.parse(input).unwrap();
+
+        let spans: Vec<_> = tokens
+            .iter()
+            .map(|token| &input[token.span.clone()])
+            .collect();
+        assert_eq!(spans, ["mul(2,4)", "don't()", "mul(5,5)", "do()"]);
+        assert_eq!(tokens[2].instruction, Instruction::Mul(5, 5));
+
+        let mut machine = Machine::new(Config { conditionals: true });
+        machine.run(&tokens[..3]).unwrap();
+        assert_eq!((machine.total, machine.enabled), (8, false));
+    }
+
+    #[test]
+    fn operand_digits() {
+        let input = "mul(999,999)mul(1000,2)mul(0012,3)mul(,4)mul(007,3)";
+        let tokens = Day03::parse.parse(input).unwrap();
+
+        let instructions: Vec<_> = tokens.iter().map(|token| token.instruction).collect();
+        assert_eq!(
+            instructions,
+            [Instruction::Mul(999, 999), Instruction::Mul(7, 3)]
+        );
+        let reasons: Vec<_> = near_misses(&tokens, input)
+            .into_iter()
+            .map(|miss| (miss.span.start, miss.reason))
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                (12, "expected ','".to_string()),
+                (23, "expected ','".to_string()),
+                (34, "expected number of 1 to 3 digits".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_near_misses() {
+        let input = "xmul(mul[3,4]mul(2,2)";
+        let tokens = Day03::parse.parse(input).unwrap();
+
+        let spans: Vec<_> = near_misses(&tokens, input)
+            .into_iter()
+            .map(|miss| miss.span)
+            .collect();
+        assert_eq!(spans, [1..6, 5..9]);
+        assert_eq!(
+            explain(&tokens, input, true).unwrap().lines().next(),
+            Some("x\x1b[4;33mmul(m\x1b[0m\x1b[4;33mul[\x1b[0m3,4]\x1b[1;32mmul(2,2)\x1b[0m")
+        );
+    }
+
+    /// Hands out at most `step` bytes per read, however many are asked for.
+    struct Trickle<'a> {
+        bytes: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.step).min(self.bytes.len());
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn stream_chunks() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\n\
+            ¿mul(999,999)ünmul(1000,1)do_not()don'don't()mul(12,3)do()mul(7,7)mul(4";
+        let tokens = Day03::parse.parse(input).unwrap();
+        let part = |conditionals| Machine::new(Config { conditionals }).run(&tokens).unwrap();
+        let (part1, part2) = (part(false), part(true));
+        let answers = [Answer::from(part1), Answer::from(part2)];
+        assert_eq!(tokens.len(), 11);
+
+        for chunk_size in (1..=20).chain([64, 4096]) {
+            let scanned: Vec<_> = Scanner::with_chunk_size(input.as_bytes(), chunk_size)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(scanned, tokens, "chunk size {chunk_size}");
+
+            for step in [1, 3, 7] {
+                let reader = || Trickle {
+                    bytes: input.as_bytes(),
+                    step,
+                };
+                let scanned: Vec<_> = Scanner::with_chunk_size(reader(), chunk_size)
+                    .collect::<io::Result<_>>()
+                    .unwrap();
+                assert_eq!(scanned, tokens, "chunk size {chunk_size}, step {step}");
+
+                assert_eq!(Day03::stream("memory", &mut reader()).unwrap(), answers);
+            }
+        }
+    }
+
+    #[test]
+    fn stream_invalid_utf8() {
+        let bytes = b"\xffmul(2,\xc3\xa9\x80mul(3,4)\xe2";
+        let tokens: Vec<_> = Scanner::with_chunk_size(&bytes[..], 5)
+            .collect::<io::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            tokens,
+            [Token {
+                instruction: Instruction::Mul(3, 4),
+                span: 10..18,
+            }]
+        );
+    }
+
+    #[test]
+    fn overflowing_total() {
+        let input = "mul(2,3)don't()mul(999,999)do()mul(4,5)";
+        let tokens = Day03::parse.parse(input).unwrap();
+
+        let mut machine = Machine::new(Config { conditionals: true });
+        machine.total = u64::MAX - 10;
+        let err = machine.run(&tokens).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "arithmetic overflow: mul(4,5) at offset 31"
+        );
+        assert_eq!(machine.total, u64::MAX - 4);
+
+        let mut machine = Machine::new(Config {
+            conditionals: false,
+        });
+        machine.total = u64::MAX - 10;
+        let err = machine.run(&tokens).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "arithmetic overflow: mul(999,999) at offset 15"
+        );
+    }
+
+    #[test]
+    fn explain_memory() {
+        let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let tokens = Day03::parse.parse(input).unwrap();
+
+        assert_eq!(
+            near_misses(&tokens, input),
+            [
+                NearMiss {
+                    span: 10..14,
+                    reason: "expected '('".to_string(),
+                },
+                NearMiss {
+                    span: 37..47,
+                    reason: "expected ')'".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            explain(&tokens, input, false).unwrap(),
+            "\
+x[mul(2,4)]&mul[3,7]!^[don't()]_{mul(5,5)}+mul(32,64]({mul(11,8)}un[do()]?[mul(8,5)])
+
+Offset  Instruction  Status    Product  Part 1  Part 2
+     1  mul(2,4)     counted         8       8       8
+    20  don't()      disable                 8       8
+    28  mul(5,5)     disabled       25      33       8
+    48  mul(11,8)    disabled       88     121       8
+    59  do()         enable                121       8
+    64  mul(8,5)     counted        40     161      48
+
+Near misses:
+10  mul[          expected '('
+37  mul(32,64]    expected ')'
+"
+        );
+
+        let coloured = explain(&tokens, input, true).unwrap();
+        assert!(coloured.contains("\x1b[31mmul(5,5)\x1b[0m"), "{coloured}");
+        assert!(
+            coloured.contains("\x1b[4;33mmul(32,64]\x1b[0m"),
+            "{coloured}"
+        );
+    }
+}
@@ -0,0 +1,347 @@
+use crate::error::{Error, ParseError, Result};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::prelude::*;
+use crate::solution::{Answer, Solution};
+
+use winnow::ascii::{line_ending, multispace0, space0};
+use winnow::combinator::{alt, delimited, repeat, separated, terminated};
+use winnow::error::{StrContext, StrContextValue};
+use winnow::prelude::*;
+use winnow::token::none_of;
+
+pub struct Day04;
+
+type Board = Grid<char>;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = Board;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Board> {
+        terminated(grid_of(none_of(['\r', '\n'])), end_of_input).parse_next(input)
+    }
+
+    fn part1(board: &Board) -> Result<Answer> {
+        Ok(process_part1(board).into())
+    }
+
+    fn part2(board: &Board) -> Result<Answer> {
+        Ok(process_part2(board).into())
+    }
+}
+
+fn process_part1(board: &Board) -> usize {
+    word_search(board, "XMAS", Directions::All, Edges::Stop).len()
+}
+
+/// Where a word was found: its first letter and the direction it reads in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: Point,
+    pub direction: Direction,
+}
+
+/// The directions a word may read in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directions {
+    /// Left to right, right to left, top to bottom and bottom to top.
+    Orthogonal,
+    /// Along both diagonals, either way.
+    Diagonal,
+    /// Orthogonally or diagonally.
+    All,
+}
+
+impl Directions {
+    fn directions(self) -> &'static [Direction] {
+        match self {
+            Directions::Orthogonal => &Direction::ALL4,
+            Directions::Diagonal => &Direction::DIAGONALS,
+            Directions::All => &Direction::ALL8,
+        }
+    }
+}
+
+/// What happens when a word reaches the edge of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edges {
+    /// The word must fit inside the grid.
+    Stop,
+    /// The word continues from the opposite edge.
+    Wrap,
+}
+
+/// Every occurrence of `word` in `grid`, in row order of the first letter and
+/// then in the order of `directions`.
+///
+/// A word that reads the same backwards is found once from each end, and a
+/// single letter is found once for every direction.
+pub fn word_search(grid: &Board, word: &str, directions: Directions, edges: Edges) -> Vec<Match> {
+    let letters: Vec<char> = word.chars().collect();
+    let Some((first, rest)) = letters.split_first() else {
+        return Vec::new();
+    };
+
+    let reads = |mut pos: Point, direction: Direction| {
+        rest.iter().all(|letter| {
+            let next = match edges {
+                Edges::Stop => grid.step(pos, direction),
+                Edges::Wrap => Some(grid.step_wrapping(pos, direction)),
+            };
+            match next {
+                Some(next) if grid[next] == *letter => {
+                    pos = next;
+                    true
+                }
+                _ => false,
+            }
+        })
+    };
+
+    grid.iter()
+        .filter(|(_, ch)| *ch == first)
+        .flat_map(|(start, _)| {
+            directions
+                .directions()
+                .iter()
+                .filter(move |direction| reads(start, **direction))
+                .map(move |direction| Match {
+                    start,
+                    direction: *direction,
+                })
+        })
+        .collect()
+}
+
+fn process_part2(board: &Board) -> usize {
+    Pattern::new("M.S / .A. / M.S")
+        .expect("X-MAS template is valid")
+        .count(board)
+}
+
+/// A rectangular shape of letters to find in a grid, written as rows separated
+/// by newlines or `/`, with `.` matching any letter. For example
+/// `M.S / .A. / M.S` is an X made of two diagonal `MAS`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    variants: Vec<Grid<Option<char>>>,
+}
+
+/// Where a pattern was found: the top left corner of the matching area, and
+/// which of [`Pattern::variants`] matched there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrence {
+    pub at: Point,
+    pub variant: usize,
+}
+
+impl Pattern {
+    /// The pattern in `template` with all its distinct rotations and
+    /// reflections.
+    pub fn new(template: &str) -> Result<Pattern> {
+        let mut pattern = Pattern::exact(template)?;
+        let shape = pattern
+            .variants
+            .pop()
+            .expect("exact pattern has one variant");
+        for mut variant in [shape.clone(), shape.flip()] {
+            for _ in 0..4 {
+                if !pattern.variants.contains(&variant) {
+                    pattern.variants.push(variant.clone());
+                }
+                variant = variant.rotate_right();
+            }
+        }
+        Ok(pattern)
+    }
+
+    /// The pattern in `template`, only as written.
+    pub fn exact(template: &str) -> Result<Pattern> {
+        let shape = pattern_rows.parse(template).map_err(|err| {
+            Error::Parse(ParseError::new(
+                "pattern",
+                template,
+                err.offset(),
+                err.inner(),
+            ))
+        })?;
+        Ok(Pattern {
+            variants: vec![shape],
+        })
+    }
+
+    /// The orientations searched for, the template as written first.
+    pub fn variants(&self) -> &[Grid<Option<char>>] {
+        &self.variants
+    }
+
+    /// Every place in `grid` matching any of the variants, in variant order
+    /// and then row order.
+    pub fn find(&self, grid: &Board) -> Vec<Occurrence> {
+        self.variants
+            .iter()
+            .enumerate()
+            .flat_map(|(variant, shape)| {
+                let xs = 0..(grid.width() + 1).saturating_sub(shape.width());
+                let ys = 0..(grid.height() + 1).saturating_sub(shape.height());
+                ys.flat_map(move |y| xs.clone().map(move |x| Point::new(x, y)))
+                    .filter(move |at| matches_at(grid, shape, *at))
+                    .map(move |at| Occurrence { at, variant })
+            })
+            .collect()
+    }
+
+    pub fn count(&self, grid: &Board) -> usize {
+        self.find(grid).len()
+    }
+}
+
+fn matches_at(grid: &Board, shape: &Grid<Option<char>>, at: Point) -> bool {
+    shape.iter().all(|(pos, letter)| {
+        letter.is_none_or(|letter| grid[Point::new(at.x + pos.x, at.y + pos.y)] == letter)
+    })
+}
+
+fn pattern_row(input: &mut Stream<'_>) -> PResult<Vec<Option<char>>> {
+    repeat(
+        1..,
+        none_of([' ', '\t', '/', '\r', '\n']).map(|c| (c != '.').then_some(c)),
+    )
+    .context(StrContext::Expected(StrContextValue::Description(
+        "letter or '.'",
+    )))
+    .parse_next(input)
+}
+
+fn pattern_rows(input: &mut Stream<'_>) -> PResult<Grid<Option<char>>> {
+    let separator = delimited(space0, alt(("/", line_ending)), space0);
+
+    delimited(space0, separated(1.., pattern_row, separator), multispace0)
+        .verify(|rows: &Vec<Vec<_>>| rows.iter().all(|row| row.len() == rows[0].len()))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "rows of equal width",
+        )))
+        .map(|rows: Vec<Vec<_>>| {
+            let (width, height) = (rows[0].len(), rows.len());
+            Grid::new(width, height, rows.concat())
+        })
+        .parse_next(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    #[test]
+    fn example_input() {
+        let board = Day04::parse.parse(EXAMPLE).unwrap();
+
+        assert_eq!(process_part1(&board), 18);
+        assert_eq!(process_part2(&board), 9);
+    }
+
+    #[rstest]
+    #[case::crlf(&EXAMPLE.replace('\n', "\r\n"), (10, 10), 18, 9)]
+    #[case::crlf_trailing(&(EXAMPLE.replace('\n', "\r\n") + "\r\n"), (10, 10), 18, 9)]
+    #[case::single_row("XMASAMX", (7, 1), 2, 0)]
+    #[case::trailing_blank("XMAS\nSAMX\n\n", (4, 2), 2, 0)]
+    #[case::trailing_blank_crlf("XMAS\r\nSAMX\r\n\r\n\r\n", (4, 2), 2, 0)]
+    #[case::single_column("X\nM\nA\nS\nA\nM\nX\n", (1, 7), 2, 0)]
+    #[case::wide("M.S.XMAS\n.A..SAMX\nM.S.....\n", (8, 3), 2, 1)]
+    #[case::tall("X..\nM..\nA..\nS.S\n.A.\nM.M\n", (3, 6), 1, 1)]
+    #[case::multibyte("ÉXMAS€\nSAMXÅS\n€ÉÉÉÉA\nÅÄÖÜßM\n", (6, 4), 2, 0)]
+    fn board_shapes(
+        #[case] input: &str,
+        #[case] size: (usize, usize),
+        #[case] xmas: usize,
+        #[case] x_mas: usize,
+    ) {
+        let board = Day04::parse.parse(input).unwrap();
+
+        assert_eq!((board.width(), board.height()), size);
+        assert_eq!(process_part1(&board), xmas, "{input:?}");
+        assert_eq!(process_part2(&board), x_mas, "{input:?}");
+    }
+
+    #[test]
+    fn patterns() {
+        let pattern = Pattern::new("M.S\n.A.\nM.S").unwrap();
+        assert_eq!(pattern.variants().len(), 4);
+        assert_eq!(
+            pattern.variants()[1],
+            Pattern::exact("M.M / .A. / S.S").unwrap().variants()[0]
+        );
+
+        let board = Day04::parse.parse("SAM.\n.A..\nSAMS\n").unwrap();
+        assert_eq!(
+            pattern.find(&board),
+            [Occurrence {
+                at: Point::new(0, 0),
+                variant: 2,
+            }]
+        );
+
+        let bar = Pattern::new("AM").unwrap();
+        assert_eq!(bar.variants().len(), 4);
+        assert_eq!(bar.count(&board), 2);
+        assert_eq!(Pattern::exact("A / M").unwrap().count(&board), 0);
+        assert_eq!(Pattern::exact(".").unwrap().count(&board), 12);
+
+        let err = Pattern::new("M.S / .A").unwrap_err();
+        assert!(err.to_string().contains("rows of equal width"), "{err}");
+    }
+
+    #[test]
+    fn search_words() {
+        let board = Day04::parse.parse("CAT\nAA.\nT.T\n").unwrap();
+        let hit = |x, y, direction| Match {
+            start: Point::new(x, y),
+            direction,
+        };
+        let search = |word, directions, edges| word_search(&board, word, directions, edges);
+
+        assert_eq!(
+            search("CAT", Directions::All, Edges::Stop),
+            [
+                hit(0, 0, Direction::Right),
+                hit(0, 0, Direction::DownRight),
+                hit(0, 0, Direction::Down),
+            ]
+        );
+        assert_eq!(search("CAT", Directions::Orthogonal, Edges::Stop).len(), 2);
+        assert_eq!(
+            search("CAT", Directions::Diagonal, Edges::Stop),
+            [hit(0, 0, Direction::DownRight)]
+        );
+        assert_eq!(
+            search("TAC", Directions::All, Edges::Stop),
+            [
+                hit(2, 0, Direction::Left),
+                hit(0, 2, Direction::Up),
+                hit(2, 2, Direction::UpLeft),
+            ]
+        );
+        assert_eq!(search("CATC", Directions::All, Edges::Stop), []);
+        assert_eq!(search("CATC", Directions::All, Edges::Wrap).len(), 3);
+        assert_eq!(
+            search("CATCA", Directions::Orthogonal, Edges::Wrap),
+            [hit(0, 0, Direction::Right), hit(0, 0, Direction::Down)]
+        );
+        assert_eq!(search("", Directions::All, Edges::Stop), []);
+    }
+}
@@ -0,0 +1,606 @@
+use crate::error::{Error, Result};
+use crate::prelude::*;
+use crate::solution::{Answer, Solution};
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use winnow::combinator::{cut_err, peek, separated, terminated};
+use winnow::error::{StrContext, StrContextValue};
+use winnow::prelude::*;
+
+pub struct Day05;
+
+#[derive(Debug)]
+pub struct Manual {
+    rules: OrderingRules,
+    pages: Vec<Vec<u32>>,
+}
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Manual;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Manual> {
+        (process_rules, process_pages)
+            .map(|(rules, pages)| Manual { rules, pages })
+            .parse_next(input)
+    }
+
+    fn part1(manual: &Manual) -> Result<Answer> {
+        Ok(process_part1(manual)?.into())
+    }
+
+    fn part2(manual: &Manual) -> Result<Answer> {
+        Ok(process_part2(manual)?.into())
+    }
+}
+
+fn process_rules(input: &mut Stream<'_>) -> PResult<OrderingRules> {
+    section(lines_of(rule))
+        .map(OrderingRules::new)
+        .parse_next(input)
+}
+
+fn process_pages(input: &mut Stream<'_>) -> PResult<Vec<Vec<u32>>> {
+    lines_of(page_list)
+        .context(StrContext::Label("page list"))
+        .parse_next(input)
+}
+
+fn rule(input: &mut Stream<'_>) -> PResult<(u32, u32)> {
+    pair_sep(page, '|'.context(StrContext::Expected('|'.into())))
+        .context(StrContext::Label("rule pair"))
+        .parse_next(input)
+}
+
+fn page_list(input: &mut Stream<'_>) -> PResult<Vec<u32>> {
+    terminated(
+        separated(1.., page, ','),
+        cut_err(peek(end_of_line)).context(StrContext::Expected(','.into())),
+    )
+    .parse_next(input)
+}
+
+fn page(input: &mut Stream<'_>) -> PResult<u32> {
+    number
+        .context(StrContext::Expected(StrContextValue::Description(
+            "page number",
+        )))
+        .parse_next(input)
+}
+
+fn process_part1(manual: &Manual) -> Result<u32> {
+    let mut total = 0;
+    for page in &manual.pages {
+        check_acyclic(&manual.rules, page)?;
+        if manual.rules.is_sorted(page) {
+            total += page[page.len() / 2];
+        }
+    }
+    Ok(total)
+}
+
+fn process_part2(manual: &Manual) -> Result<u32> {
+    let mut total = 0;
+    for page in &manual.pages {
+        check_acyclic(&manual.rules, page)?;
+        if manual.rules.is_sorted(page) {
+            continue;
+        }
+        let mut ordered = page.clone();
+        manual.rules.sort(&mut ordered);
+        total += ordered[ordered.len() / 2];
+    }
+    Ok(total)
+}
+
+/// Fails if the rules for an update are cyclic, so no order of it is right.
+fn check_acyclic(rules: &OrderingRules, page: &[u32]) -> Result<()> {
+    match rules.cycle(page) {
+        Some(chain) => {
+            let chain = chain.iter().map(u32::to_string).collect::<Vec<_>>();
+            Err(Error::NoAnswer(format!(
+                "the rules for update {page:?} are cyclic: {}",
+                chain.join(" -> ")
+            )))
+        }
+        None => Ok(()),
+    }
+}
+
+/// The page ordering rules, as a graph with an edge from each page to the
+/// pages that must come after it.
+///
+/// Queries take the set of pages they apply to, because only the rules
+/// between those pages count: the full rule set of a real input is cyclic,
+/// but any one update must not be.
+#[derive(Debug, Default)]
+pub struct OrderingRules {
+    rules: HashSet<(u32, u32)>,
+    successors: BTreeMap<u32, Vec<u32>>,
+}
+
+impl OrderingRules {
+    pub fn new(rules: impl IntoIterator<Item = (u32, u32)>) -> Self {
+        let rules: HashSet<_> = rules.into_iter().collect();
+        let mut successors = BTreeMap::<u32, Vec<u32>>::new();
+        for &(before, after) in &rules {
+            successors.entry(before).or_default().push(after);
+        }
+        for next in successors.values_mut() {
+            next.sort_unstable();
+        }
+        OrderingRules { rules, successors }
+    }
+
+    /// Whether there is a rule `before|after`.
+    pub fn has_rule(&self, before: u32, after: u32) -> bool {
+        self.rules.contains(&(before, after))
+    }
+
+    /// Whether a chain of rules between `pages` forces `a` before `b`.
+    pub fn must_precede(&self, a: u32, b: u32, pages: &[u32]) -> bool {
+        self.explain(a, b, pages).is_some()
+    }
+
+    /// The shortest chain of rules between `pages` forcing `a` before `b`,
+    /// from `a` to `b` inclusive.
+    pub fn explain(&self, a: u32, b: u32, pages: &[u32]) -> Option<Vec<u32>> {
+        let subset: HashSet<_> = pages.iter().copied().collect();
+        if !subset.contains(&a) || !subset.contains(&b) {
+            return None;
+        }
+
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::from([a]);
+        while let Some(page) = queue.pop_front() {
+            for &next in self.next(page, &subset) {
+                if next == a || previous.contains_key(&next) {
+                    continue;
+                }
+                previous.insert(next, page);
+                if next == b {
+                    let mut chain = vec![b];
+                    while let Some(&page) = previous.get(chain.last()?) {
+                        chain.push(page);
+                    }
+                    chain.reverse();
+                    return Some(chain);
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+
+    /// Orders two pages by the rule between them, or `Equal` if there is no
+    /// rule or there are rules both ways.
+    ///
+    /// This is only a total order over pages that have a rule for every pair
+    /// and no cycles, as in each update of a real input.
+    pub fn compare(&self, a: u32, b: u32) -> Ordering {
+        match (self.has_rule(a, b), self.has_rule(b, a)) {
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            _ => Ordering::Equal,
+        }
+    }
+
+    /// Whether no page in `pages` must come before one listed earlier, like
+    /// [`is_ordered`](Self::is_ordered), for pages whose rules have no
+    /// [`cycle`](Self::cycle).
+    ///
+    /// Only neighbouring pages are compared, falling back to `is_ordered` when
+    /// a pair of them has no rule. Without cycles that is enough: a rule
+    /// against the order of pages that are each before the next would close
+    /// a cycle.
+    pub fn is_sorted(&self, pages: &[u32]) -> bool {
+        let mut fallback = false;
+        let sorted = pages.is_sorted_by(|a, b| match self.compare(*a, *b) {
+            Ordering::Less => true,
+            Ordering::Equal => {
+                fallback = true;
+                true
+            }
+            Ordering::Greater => false,
+        });
+        sorted && (!fallback || self.is_ordered(pages))
+    }
+
+    /// Sorts `pages`, whose rules must have no [`cycle`](Self::cycle), so that
+    /// every rule between them holds.
+    ///
+    /// Pages are sorted by how many of the others must directly precede them,
+    /// which is right when every pair has a rule, as in a real input. If
+    /// [`is_sorted`](Self::is_sorted) then finds a pair out of order, they
+    /// are put in [`total_order`](Self::total_order) instead. Pages with
+    /// cyclic rules are left in an unspecified order.
+    pub fn sort(&self, pages: &mut [u32]) {
+        let subset: HashSet<_> = pages.iter().copied().collect();
+        let mut preceding = HashMap::<u32, usize>::new();
+        for &page in &subset {
+            for &next in self.next(page, &subset) {
+                *preceding.entry(next).or_default() += 1;
+            }
+        }
+        pages.sort_by_key(|page| preceding.get(page).copied().unwrap_or_default());
+
+        if !self.is_sorted(pages) {
+            if let Some(order) = self.total_order(pages) {
+                pages.copy_from_slice(&order);
+            }
+        }
+    }
+
+    /// Whether no page in `pages` must come before one listed earlier.
+    pub fn is_ordered(&self, pages: &[u32]) -> bool {
+        self.total_order(pages).is_some_and(|order| order == pages)
+    }
+
+    /// `pages` in an order that satisfies every rule between them, or `None`
+    /// if their rules are cyclic. Pages the rules leave free keep their
+    /// relative order, so an update that is already ordered is unchanged.
+    pub fn total_order(&self, pages: &[u32]) -> Option<Vec<u32>> {
+        // Position of each page's first appearance, to keep free pages in
+        // their original order.
+        let mut index = HashMap::new();
+        for (idx, page) in pages.iter().enumerate() {
+            index.entry(*page).or_insert(idx);
+        }
+        let subset: HashSet<_> = index.keys().copied().collect();
+        let mut waiting: HashMap<u32, usize> = subset.iter().map(|page| (*page, 0)).collect();
+        for &page in &subset {
+            for next in self.next(page, &subset) {
+                *waiting.get_mut(next)? += 1;
+            }
+        }
+
+        let mut ready: BinaryHeap<_> = index
+            .iter()
+            .filter(|(page, _)| waiting[page] == 0)
+            .map(|(page, idx)| Reverse((*idx, *page)))
+            .collect();
+        let mut order = Vec::with_capacity(subset.len());
+        while let Some(Reverse((_, page))) = ready.pop() {
+            order.push(page);
+            for next in self.next(page, &subset) {
+                let count = waiting.get_mut(next)?;
+                *count -= 1;
+                if *count == 0 {
+                    ready.push(Reverse((index[next], *next)));
+                }
+            }
+        }
+        (order.len() == subset.len()).then_some(order)
+    }
+
+    /// A chain of rules between `pages` that loops back on itself, starting
+    /// and ending on the same page.
+    pub fn cycle(&self, pages: &[u32]) -> Option<Vec<u32>> {
+        let subset = pages.iter().copied().collect();
+        let mut marks = HashMap::new();
+        for &page in pages {
+            if !marks.contains_key(&page) {
+                let cycle = self.visit(page, &subset, &mut marks, &mut Vec::new());
+                if cycle.is_some() {
+                    return cycle;
+                }
+            }
+        }
+        None
+    }
+
+    /// Depth-first search for [`cycle`](Self::cycle), with `path` holding the
+    /// pages currently being visited.
+    fn visit(
+        &self,
+        page: u32,
+        subset: &HashSet<u32>,
+        marks: &mut HashMap<u32, Mark>,
+        path: &mut Vec<u32>,
+    ) -> Option<Vec<u32>> {
+        marks.insert(page, Mark::Visiting);
+        path.push(page);
+        for &next in self.next(page, subset) {
+            match marks.get(&next) {
+                Some(Mark::Visiting) => {
+                    let start = path.iter().position(|p| *p == next)?;
+                    let mut chain = path[start..].to_vec();
+                    chain.push(next);
+                    return Some(chain);
+                }
+                Some(Mark::Done) => {}
+                None => {
+                    if let Some(chain) = self.visit(next, subset, marks, path) {
+                        return Some(chain);
+                    }
+                }
+            }
+        }
+        path.pop();
+        marks.insert(page, Mark::Done);
+        None
+    }
+
+    /// The pages in `subset` that must come after `page`.
+    fn next<'a>(
+        &'a self,
+        page: u32,
+        subset: &'a HashSet<u32>,
+    ) -> impl Iterator<Item = &'a u32> + 'a {
+        self.successors
+            .get(&page)
+            .into_iter()
+            .flatten()
+            .filter(|next| subset.contains(next))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Mark {
+    Visiting,
+    Done,
+}
+
+impl Manual {
+    /// Checks the rules and updates for anything that would make the ordering
+    /// or the middle page ill-defined.
+    pub fn analyse(&self) -> Analysis {
+        let mut contradictions: Vec<_> = self
+            .rules
+            .rules
+            .iter()
+            .filter(|(a, b)| a < b && self.rules.has_rule(*b, *a))
+            .copied()
+            .collect();
+        contradictions.sort_unstable();
+
+        let mut analysis = Analysis {
+            contradictions,
+            ..Analysis::default()
+        };
+        for (update, page) in self.pages.iter().enumerate() {
+            if let Some(chain) = self.rules.cycle(page) {
+                analysis.cycles.push((update, chain));
+            }
+            for (idx, a) in page.iter().enumerate() {
+                for b in &page[idx + 1..] {
+                    if !self.rules.has_rule(*a, *b) && !self.rules.has_rule(*b, *a) {
+                        analysis.unordered.push((update, (*a, *b)));
+                    }
+                }
+            }
+            if page.len() % 2 == 0 {
+                analysis.ambiguous.push(update);
+            }
+        }
+        analysis
+    }
+}
+
+/// Problems found by [`Manual::analyse`]. Updates are identified by their
+/// index in the input, and reported counting from 1.
+#[derive(Debug, Default, PartialEq)]
+pub struct Analysis {
+    /// Pairs of pages with rules in both directions, smaller page first.
+    pub contradictions: Vec<(u32, u32)>,
+    /// Updates whose pages' rules form a cycle, with the chain of pages such
+    /// that each must precede the next, starting and ending on the same page.
+    pub cycles: Vec<(usize, Vec<u32>)>,
+    /// Pages that appear together in an update with no rule between them.
+    pub unordered: Vec<(usize, (u32, u32))>,
+    /// Updates with an even number of pages, which have no single middle page.
+    pub ambiguous: Vec<usize>,
+}
+
+impl Analysis {
+    pub fn is_clean(&self) -> bool {
+        *self == Analysis::default()
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (a, b) in &self.contradictions {
+            writeln!(f, "contradictory rules {a}|{b} and {b}|{a}")?;
+        }
+        for (update, chain) in &self.cycles {
+            let chain = chain.iter().map(u32::to_string).collect::<Vec<_>>();
+            writeln!(f, "update {}: cycle {}", update + 1, chain.join(" -> "))?;
+        }
+        for (update, (a, b)) in &self.unordered {
+            writeln!(f, "update {}: no rule orders {a} and {b}", update + 1)?;
+        }
+        for update in &self.ambiguous {
+            writeln!(f, "update {}: even length, no middle page", update + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ParseError;
+    use rstest::rstest;
+
+    #[test]
+    fn test_process_rules() {
+        let input = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+        let manual = Day05::parse.parse(input).unwrap();
+
+        assert_eq!(Day05::part1(&manual).unwrap(), Answer::Number(143));
+        assert_eq!(Day05::part2(&manual).unwrap(), Answer::Number(123));
+        assert!(manual.analyse().is_clean(), "{}", manual.analyse());
+    }
+
+    #[test]
+    fn analyse_rules() {
+        let input = "13|29
+29|47
+47|13
+1|2
+2|1
+5|6
+
+29,13,47
+1,2,5
+5,6
+";
+        let manual = Day05::parse.parse(input).unwrap();
+        let analysis = manual.analyse();
+
+        assert_eq!(analysis.contradictions, [(1, 2)]);
+        assert_eq!(
+            analysis.cycles,
+            [(0, vec![29, 47, 13, 29]), (1, vec![1, 2, 1])]
+        );
+        assert_eq!(analysis.unordered, [(1, (1, 5)), (1, (2, 5))]);
+        assert_eq!(analysis.ambiguous, [2]);
+        assert_eq!(
+            analysis.to_string(),
+            "\
+contradictory rules 1|2 and 2|1
+update 1: cycle 29 -> 47 -> 13 -> 29
+update 2: cycle 1 -> 2 -> 1
+update 2: no rule orders 1 and 5
+update 2: no rule orders 2 and 5
+update 3: even length, no middle page
+"
+        );
+        assert!(Day05::part1(&manual).is_err());
+        assert!(Day05::part2(&manual).is_err());
+
+        // A cycle listed in chain order has every neighbouring pair in order.
+        let manual = Day05::parse
+            .parse("29|47\n47|13\n13|29\n\n29,47,13\n")
+            .unwrap();
+        assert_eq!(
+            manual.analyse().to_string(),
+            "update 1: cycle 29 -> 47 -> 13 -> 29\n"
+        );
+        assert_eq!(
+            Day05::part1(&manual).unwrap_err().to_string(),
+            "no answer: the rules for update [29, 47, 13] are cyclic: 29 -> 47 -> 13 -> 29"
+        );
+        assert!(Day05::part2(&manual).is_err());
+    }
+
+    #[test]
+    fn large_updates() {
+        // Longer than 20 pages, where `sort_by` stops using insertion sort and
+        // may panic on a comparator that is not a total order, as the
+        // comparator is here.
+        let chain = (1..40).map(|page| (page, page + 1));
+        let mut update: Vec<u32> = (1..=40).collect();
+        let mut state = 2u32;
+        for i in (1..update.len()).rev() {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            update.swap(i, (state >> 16) as usize % (i + 1));
+        }
+        let update = update
+            .iter()
+            .map(u32::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        let rules = |rules: Vec<(u32, u32)>| {
+            rules
+                .iter()
+                .map(|(a, b)| format!("{a}|{b}\n"))
+                .collect::<String>()
+        };
+
+        let input = format!("{}\n{update}\n1,2,3\n", rules(chain.clone().collect()));
+        let manual = Day05::parse.parse(&input).unwrap();
+        assert_eq!(Day05::part1(&manual).unwrap(), Answer::Number(2));
+        assert_eq!(Day05::part2(&manual).unwrap(), Answer::Number(21));
+
+        let input = format!("{}\n{update}\n", rules(chain.chain([(40, 1)]).collect()));
+        let manual = Day05::parse.parse(&input).unwrap();
+        assert!(Day05::part1(&manual).is_err());
+        assert!(Day05::part2(&manual).is_err());
+        let mut pages = manual.pages[0].clone();
+        manual.rules.sort(&mut pages);
+    }
+
+    #[test]
+    fn ordering_queries() {
+        let rules = OrderingRules::new([(47, 53), (97, 61), (61, 53), (53, 29), (97, 47)]);
+        let pages = [53, 29, 47, 61, 97];
+
+        assert!(rules.must_precede(97, 29, &pages));
+        assert!(!rules.must_precede(29, 97, &pages));
+        assert!(!rules.must_precede(97, 29, &[97, 13, 29]));
+        assert_eq!(rules.explain(97, 29, &pages), Some(vec![97, 47, 53, 29]));
+        assert_eq!(rules.explain(61, 29, &pages), Some(vec![61, 53, 29]));
+        assert_eq!(rules.explain(47, 29, &pages), Some(vec![47, 53, 29]));
+        assert_eq!(rules.explain(47, 61, &pages), None);
+
+        assert_eq!(rules.total_order(&pages), Some(vec![97, 47, 61, 53, 29]));
+        assert!(rules.is_ordered(&[97, 47, 61, 53, 29]));
+        assert!(!rules.is_ordered(&pages));
+        assert!(rules.cycle(&pages).is_none());
+        let mut sorted = pages;
+        rules.sort(&mut sorted);
+        assert!(rules.is_ordered(&sorted), "{sorted:?}");
+
+        // The comparator needs a rule for every pair, as in the real input.
+        let order = [97, 75, 47, 61, 53];
+        let complete = OrderingRules::new(
+            (0..order.len()).flat_map(|i| order[i + 1..].iter().map(move |b| (order[i], *b))),
+        );
+        let mut update = [75, 97, 47, 61, 53];
+        assert!(!complete.is_sorted(&update));
+        assert_eq!(complete.compare(97, 75), Ordering::Less);
+        complete.sort(&mut update);
+        assert_eq!(update, order);
+        assert!(complete.is_sorted(&update));
+
+        let cyclic = OrderingRules::new([(1, 2), (2, 3), (3, 1)]);
+        assert_eq!(cyclic.total_order(&[1, 2, 3]), None);
+        assert_eq!(cyclic.total_order(&[3, 1]), Some(vec![3, 1]));
+        assert_eq!(cyclic.cycle(&[2, 3, 1]), Some(vec![2, 3, 1, 2]));
+    }
+
+    #[rstest]
+    #[case("47|53\n97x13\n\n75,47\n", 2, 3)]
+    #[case("47|53\n97|13\n75,47\n", 3, 3)]
+    #[case("47|53\n\n75,47,61\n97,61;53\n", 4, 6)]
+    #[case("47|53\n\n75,47,61\n\n97,61\n", 4, 1)]
+    fn malformed(#[case] input: &str, #[case] line: usize, #[case] column: usize) {
+        let err = Day05::parse.parse(input).unwrap_err();
+        let err = ParseError::new("input", input, err.offset(), err.inner());
+        assert_eq!((err.line, err.column), (line, column), "{err}");
+    }
+}
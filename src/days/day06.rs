@@ -0,0 +1,530 @@
+use crate::error::{Error, Result};
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::prelude::*;
+use crate::solution::{Answer, Solution};
+
+use std::io;
+
+use winnow::combinator::{alt, opt, terminated};
+use winnow::error::{AddContext, ContextError, ErrMode, StrContext, StrContextValue};
+use winnow::prelude::*;
+use winnow::stream::Stream as _;
+
+pub struct Day06;
+
+#[derive(Debug)]
+pub struct Lab {
+    map: Map,
+    guard: (Direction, Point),
+}
+
+impl Lab {
+    /// Walks the guard, with an extra wall at `obstruction` if given, and
+    /// draws the map with its route to `out`.
+    ///
+    /// Cells the guard only crossed vertically are `|`, horizontally `-`, and
+    /// both (turns and crossings) `+`. The guard is drawn as `^`, `>`, `v` or
+    /// `<` where it left the map or closed its loop, and the obstruction as
+    /// `O`. With `colour`, the route, guard and obstruction are highlighted
+    /// with ANSI escapes.
+    ///
+    /// An obstruction outside the map, on a wall or on the guard's starting
+    /// cell is an [`Error::InvalidArgument`].
+    pub fn trace(
+        &self,
+        obstruction: Option<Point>,
+        colour: bool,
+        out: &mut impl io::Write,
+    ) -> Result<Outcome> {
+        let (direction, pos) = self.guard;
+        let mut map = self.map.clone();
+        if let Some(at) = obstruction {
+            let invalid = |reason| {
+                Error::InvalidArgument(format!(
+                    "cannot place an obstruction at ({}, {}): {reason}",
+                    at.x, at.y
+                ))
+            };
+            match map.get_mut(at) {
+                None => return Err(invalid("outside the map")),
+                Some(Cell::Wall) => return Err(invalid("there is a wall there")),
+                Some(_) if at == pos => return Err(invalid("the guard starts there")),
+                Some(cell) => *cell = Cell::Wall,
+            }
+        }
+        let outcome = navigate_map(&mut map, direction, pos);
+
+        let guard = match outcome {
+            Outcome::Exited { at, direction, .. } | Outcome::Looped { at, direction, .. } => {
+                (at, direction)
+            }
+        };
+        render(&map, guard, obstruction, colour, out).map_err(|source| Error::Io {
+            name: "trace output".to_string(),
+            source,
+        })?;
+        Ok(outcome)
+    }
+}
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Input = Lab;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Lab> {
+        terminated(parse_lab, end_of_input)
+            .context(StrContext::Label("map"))
+            .parse_next(input)
+    }
+
+    fn part1(lab: &Lab) -> Result<Answer> {
+        Ok(guard_path(lab)?.into())
+    }
+
+    fn part2(lab: &Lab) -> Result<Answer> {
+        // Walking the original path must terminate before trying obstructions.
+        guard_path(lab)?;
+        let (direction, pos) = lab.guard;
+        Ok(count_loops(&lab.map, direction, pos).into())
+    }
+}
+
+/// The number of cells the guard visits before leaving the map.
+fn guard_path(lab: &Lab) -> Result<usize> {
+    let mut map = lab.map.clone();
+    let (direction, pos) = lab.guard;
+    match navigate_map(&mut map, direction, pos) {
+        Outcome::Exited { visited, .. } => Ok(visited),
+        Outcome::Looped { at, direction, .. } => Err(Error::NoAnswer(format!(
+            "the guard never leaves the map, looping at {},{} facing {direction:?}",
+            at.x, at.y
+        ))),
+    }
+}
+
+type Map = Grid<Cell>;
+
+#[derive(PartialEq, Debug, Clone)]
+enum Cell {
+    Wall,
+    Space(Visits),
+}
+
+/// The directions the guard has faced while standing on a cell.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+struct Visits(u8);
+
+impl Visits {
+    const NONE: Visits = Visits(0);
+
+    fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    fn contains(self, direction: Direction) -> bool {
+        self.0 & 1 << direction as u8 != 0
+    }
+
+    /// Records `direction`, returning `false` if it was already recorded.
+    fn insert(&mut self, direction: Direction) -> bool {
+        let bit = 1 << direction as u8;
+        let new = self.0 & bit == 0;
+        self.0 |= bit;
+        new
+    }
+}
+
+/// How the guard's walk ended.
+#[derive(PartialEq, Debug)]
+pub enum Outcome {
+    /// The guard walked off the map from `at` facing `direction`, after
+    /// visiting `visited` distinct cells.
+    Exited {
+        visited: usize,
+        at: Point,
+        direction: Direction,
+    },
+    /// The guard came back to `at` facing `direction` after `steps` moves, and
+    /// will walk the same loop forever.
+    Looped {
+        at: Point,
+        direction: Direction,
+        steps: usize,
+    },
+}
+
+fn parse_cell(input: &mut Stream<'_>) -> PResult<Cell> {
+    alt((
+        ".".map(|_| Cell::Space(Visits::NONE)),
+        "#".map(|_| Cell::Wall),
+    ))
+    .parse_next(input)
+}
+
+fn parse_guard(input: &mut Stream<'_>) -> PResult<Direction> {
+    alt((
+        "^".map(|_| Direction::Up),
+        ">".map(|_| Direction::Right),
+        "v".map(|_| Direction::Down),
+        "<".map(|_| Direction::Left),
+    ))
+    .parse_next(input)
+}
+
+/// Parses the map, taking the guard off it. There must be exactly one guard.
+fn parse_lab(input: &mut Stream<'_>) -> PResult<Lab> {
+    let start = input.checkpoint();
+    let mut cells = 0;
+    let mut guard = None;
+
+    let map = grid_of(|input: &mut Stream<'_>| {
+        let before = input.checkpoint();
+        let cell = match opt(parse_guard).parse_next(input)? {
+            Some(_) if guard.is_some() => {
+                input.reset(&before);
+                return Err(ErrMode::Cut(ContextError::new().add_context(
+                    input,
+                    &before,
+                    StrContext::Expected(StrContextValue::Description("only one guard")),
+                )));
+            }
+            Some(direction) => {
+                guard = Some((direction, cells));
+                Cell::Space(Visits::NONE)
+            }
+            None => parse_cell(input)?,
+        };
+        cells += 1;
+        Ok(cell)
+    })
+    .parse_next(input)?;
+
+    let Some((direction, index)) = guard else {
+        input.reset(&start);
+        return Err(ErrMode::Cut(ContextError::new().add_context(
+            input,
+            &start,
+            StrContext::Expected(StrContextValue::Description("a guard")),
+        )));
+    };
+    let pos = Point::new(index % map.width(), index / map.width());
+
+    Ok(Lab {
+        map,
+        guard: (direction, pos),
+    })
+}
+
+/// Walks the guard until it leaves the map or repeats a position and
+/// direction, marking every cell it stands on.
+fn navigate_map(map: &mut Map, mut guard: Direction, mut pos: Point) -> Outcome {
+    let mut visited = 0;
+    let mut steps = 0;
+    loop {
+        let Cell::Space(visits) = &mut map[pos] else {
+            unreachable!("the guard never stands on a wall");
+        };
+        if visits.is_empty() {
+            visited += 1;
+        }
+        if !visits.insert(guard) {
+            return Outcome::Looped {
+                at: pos,
+                direction: guard,
+                steps,
+            };
+        }
+
+        match map.step(pos, guard) {
+            None => {
+                return Outcome::Exited {
+                    visited,
+                    at: pos,
+                    direction: guard,
+                }
+            }
+            Some(next) if map[next] == Cell::Wall => guard = guard.turn_right(),
+            Some(next) => {
+                pos = next;
+                steps += 1;
+            }
+        }
+    }
+}
+
+/// Walks the guard's path and, for every cell on it, checks whether an
+/// obstruction there would trap the guard in a loop.
+///
+/// The guard reaches each cell first from the state just before it, so the
+/// simulation for an obstruction starts there rather than at the beginning.
+fn count_loops(map: &Map, mut guard: Direction, mut pos: Point) -> usize {
+    let (width, height) = (map.width(), map.height());
+    let mut tried = Grid::new(width, height, vec![false; width * height]);
+    let mut seen = Grid::new(width, height, vec![0; width * height]);
+    tried[pos] = true;
+
+    let mut count = 0;
+    while let Some(next) = map.step(pos, guard) {
+        if map[next] == Cell::Wall {
+            guard = guard.turn_right();
+            continue;
+        }
+        if !tried[next] {
+            tried[next] = true;
+            if loops(map, guard, pos, next, &mut seen) {
+                count += 1;
+            }
+        }
+        pos = next;
+    }
+    count
+}
+
+/// Whether the guard walks forever with an extra wall at `obstruction`.
+///
+/// A loop is found when the guard turns at the same cell facing the same way
+/// twice. `seen` holds one bit per direction for each cell and is left cleared.
+fn loops(
+    map: &Map,
+    mut guard: Direction,
+    mut pos: Point,
+    obstruction: Point,
+    seen: &mut Grid<u8>,
+) -> bool {
+    let mut touched = Vec::new();
+    let looped = loop {
+        let Some(next) = map.step(pos, guard) else {
+            break false;
+        };
+        if next != obstruction && map[next] != Cell::Wall {
+            pos = next;
+            continue;
+        }
+
+        let bit = 1 << guard as u8;
+        if seen[pos] & bit != 0 {
+            break true;
+        }
+        if seen[pos] == 0 {
+            touched.push(pos);
+        }
+        seen[pos] |= bit;
+        guard = guard.turn_right();
+    };
+
+    for pos in touched {
+        seen[pos] = 0;
+    }
+    looped
+}
+
+const RESET: &str = "\x1b[0m";
+const ROUTE: &str = "\x1b[33m";
+const GUARD: &str = "\x1b[1;32m";
+const OBSTRUCTION: &str = "\x1b[1;31m";
+
+fn render(
+    map: &Map,
+    guard: (Point, Direction),
+    obstruction: Option<Point>,
+    colour: bool,
+    out: &mut impl io::Write,
+) -> io::Result<()> {
+    for y in 0..map.height() {
+        for x in 0..map.width() {
+            let pos = Point::new(x, y);
+            let (glyph, style) = if Some(pos) == obstruction {
+                ('O', OBSTRUCTION)
+            } else if pos == guard.0 {
+                (heading(guard.1), GUARD)
+            } else {
+                match map[pos] {
+                    Cell::Wall => ('#', ""),
+                    Cell::Space(visits) => route(visits),
+                }
+            };
+
+            if colour && !style.is_empty() {
+                write!(out, "{style}{glyph}{RESET}")?;
+            } else {
+                write!(out, "{glyph}")?;
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn route(visits: Visits) -> (char, &'static str) {
+    let vertical = visits.contains(Direction::Up) || visits.contains(Direction::Down);
+    let horizontal = visits.contains(Direction::Left) || visits.contains(Direction::Right);
+    match (vertical, horizontal) {
+        (false, false) => ('.', ""),
+        (true, false) => ('|', ROUTE),
+        (false, true) => ('-', ROUTE),
+        (true, true) => ('+', ROUTE),
+    }
+}
+
+fn heading(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+        _ => unreachable!("the guard only faces orthogonal directions"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ParseError;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    #[test]
+    fn parse_example_map() {
+        let lab = Day06::parse.parse(EXAMPLE).unwrap();
+        let mut map = lab.map.clone();
+
+        assert_eq!(map.height(), 10);
+        assert_eq!(
+            map.row(6).unwrap(),
+            [
+                Cell::Space(Visits::NONE),
+                Cell::Wall,
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE),
+                Cell::Space(Visits::NONE)
+            ]
+        );
+        assert_eq!(lab.guard, (Direction::Up, Point::new(4, 6)));
+        let trailing = Day06::parse.parse(&format!("{EXAMPLE}\n\n")).unwrap();
+        assert_eq!(trailing.map, lab.map);
+
+        let (direction, pos) = lab.guard;
+        let result = navigate_map(&mut map, direction, pos);
+
+        assert_eq!(
+            result,
+            Outcome::Exited {
+                visited: 41,
+                at: Point::new(7, 9),
+                direction: Direction::Down,
+            }
+        );
+    }
+
+    #[test]
+    fn trace_loop_candidate() {
+        let lab = Day06::parse.parse(EXAMPLE).unwrap();
+        let mut out = Vec::new();
+        let outcome = lab.trace(Some(Point::new(3, 6)), false, &mut out).unwrap();
+
+        assert!(matches!(outcome, Outcome::Looped { .. }), "{outcome:?}");
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+....|..#|.
+....|...|.
+.#.O^---+.
+........#.
+#.........
+......#...
+"
+        );
+
+        let mut out = Vec::new();
+        lab.trace(None, true, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("\x1b[1;32mv\x1b[0m"), "{out}");
+    }
+
+    #[rstest]
+    #[case(
+        Point::new(4, 6),
+        "cannot place an obstruction at (4, 6): the guard starts there"
+    )]
+    #[case(
+        Point::new(10, 3),
+        "cannot place an obstruction at (10, 3): outside the map"
+    )]
+    #[case(
+        Point::new(4, 0),
+        "cannot place an obstruction at (4, 0): there is a wall there"
+    )]
+    fn trace_invalid_obstruction(#[case] at: Point, #[case] message: &str) {
+        let lab = Day06::parse.parse(EXAMPLE).unwrap();
+        let mut out = Vec::new();
+        let err = lab.trace(Some(at), false, &mut out).unwrap_err();
+
+        assert!(matches!(err, Error::InvalidArgument(_)), "{err:?}");
+        assert_eq!(err.to_string(), format!("invalid argument: {message}"));
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn loop_obstructions() {
+        let lab = Day06::parse.parse(EXAMPLE).unwrap();
+
+        assert_eq!(Day06::part2(&lab).unwrap(), Answer::Number(6));
+    }
+
+    #[test]
+    fn trapped_guard() {
+        let input = ".#..\n...#\n#^..\n..#.\n";
+        let lab = Day06::parse.parse(input).unwrap();
+        let mut map = lab.map.clone();
+        let (direction, pos) = lab.guard;
+
+        assert_eq!(
+            navigate_map(&mut map, direction, pos),
+            Outcome::Looped {
+                at: Point::new(1, 2),
+                direction: Direction::Up,
+                steps: 4,
+            }
+        );
+        assert!(Day06::part1(&lab).is_err());
+        assert!(Day06::part2(&lab).is_err());
+    }
+
+    #[rstest]
+    #[case("..#\n...\n...\n", 1, 1, "expected a guard in map")]
+    #[case("..^\n...\n<..\n", 3, 1, "expected only one guard in map")]
+    fn guard_count(
+        #[case] input: &str,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] message: &str,
+    ) {
+        let err = Day06::parse.parse(input).unwrap_err();
+        let err = ParseError::new("input", input, err.offset(), err.inner());
+        assert_eq!((err.line, err.column), (line, column), "{err}");
+        assert_eq!(err.message, message);
+    }
+}
@@ -0,0 +1,152 @@
+use std::{fmt, io};
+
+use winnow::error::{ContextError, StrContext, StrContextValue};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The input named `name` could not be read.
+    Io { name: String, source: io::Error },
+    /// The input could not be parsed.
+    Parse(ParseError),
+    /// The part has not been solved yet.
+    Unimplemented,
+    /// The input parsed but the puzzle has no answer for it.
+    NoAnswer(String),
+    /// An answer or intermediate total does not fit in its integer type.
+    Overflow(String),
+    /// A query about a parsed input was given an argument that does not fit
+    /// it.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { name, source } => write!(f, "cannot read {name}: {source}"),
+            Error::Parse(err) => err.fmt(f),
+            Error::Unimplemented => f.write_str("not implemented"),
+            Error::NoAnswer(reason) => write!(f, "no answer: {reason}"),
+            Error::Overflow(at) => write!(f, "arithmetic overflow: {at}"),
+            Error::InvalidArgument(reason) => write!(f, "invalid argument: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// A parse failure located in its input, rendered as `name:line:column: message`
+/// followed by the offending line and a caret.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub name: String,
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(name: &str, text: &str, offset: usize, err: &ContextError) -> Self {
+        let offset = offset.min(text.len());
+        let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = text[offset..].find('\n').map_or(text.len(), |i| offset + i);
+
+        ParseError {
+            name: name.to_string(),
+            line: text[..line_start].matches('\n').count() + 1,
+            column: text[line_start..offset].chars().count() + 1,
+            source_line: text[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            message: describe(err),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(
+            f,
+            "{}:{}:{}: {}",
+            self.name, self.line, self.column, self.message
+        )?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(err: &ContextError) -> String {
+    // Contexts are recorded innermost first, so the outer, more specific
+    // expectations added by a caller are listed first.
+    let mut expected: Vec<String> = err
+        .context()
+        .filter_map(|context| match context {
+            StrContext::Expected(value) => Some(expected(value)),
+            _ => None,
+        })
+        .collect();
+    expected.reverse();
+    let label = err.context().find_map(|context| match context {
+        StrContext::Label(label) => Some(*label),
+        _ => None,
+    });
+
+    let mut message = match expected.as_slice() {
+        [] => "unexpected input".to_string(),
+        [one] => format!("expected {one}"),
+        [init @ .., last] => format!("expected {} or {last}", init.join(", ")),
+    };
+    if let Some(label) = label {
+        message.push_str(" in ");
+        message.push_str(label);
+    }
+    message
+}
+
+fn expected(value: &StrContextValue) -> String {
+    match value {
+        StrContextValue::CharLiteral('\n') => "newline".to_string(),
+        StrContextValue::CharLiteral(c) => format!("{c:?}"),
+        StrContextValue::StringLiteral(s) => format!("{s:?}"),
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day05::Day05;
+    use crate::solution::Solution;
+    use winnow::prelude::*;
+
+    #[test]
+    fn render_parse_error() {
+        let text = "47|53\r\n\r\n75,47,61\r\n97,61x53\r\n";
+        let err = Day05::parse.parse(text).unwrap_err();
+
+        let err = ParseError::new("data/day05.txt", text, err.offset(), err.inner());
+
+        assert_eq!((err.line, err.column), (4, 6));
+        assert_eq!(
+            err.to_string(),
+            "\
+data/day05.txt:4:6: expected ',' or newline in page list
+  |
+4 | 97,61x53
+  |      ^"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A position on a grid; `x` grows to the right and `y` grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+/// A displacement between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Vector {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Point { x, y }
+    }
+
+    /// `self + vector`, or `None` if either coordinate would become negative.
+    pub fn checked_add(self, vector: Vector) -> Option<Point> {
+        Some(Point {
+            x: self.x.checked_add_signed(vector.dx)?,
+            y: self.y.checked_add_signed(vector.dy)?,
+        })
+    }
+
+    /// The neighbouring point in `direction`, or `None` past the top or left edge.
+    /// Use [`Grid::step`](crate::grid::Grid::step) to also stay inside a grid.
+    pub fn step(self, direction: Direction) -> Option<Point> {
+        self.checked_add(direction.delta())
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point { x, y }
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        Vector {
+            dx: self.x as isize - other.x as isize,
+            dy: self.y as isize - other.y as isize,
+        }
+    }
+}
+
+impl Vector {
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Vector { dx, dy }
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Vector {
+        Vector::new(self.dx + other.dx, self.dy + other.dy)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Vector {
+        Vector::new(self.dx - other.dx, self.dy - other.dy)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.dx, -self.dy)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, n: isize) -> Vector {
+        Vector::new(self.dx * n, self.dy * n)
+    }
+}
+
+/// A compass direction on a grid, orthogonal or diagonal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The orthogonal directions, clockwise from `Up`.
+    pub const ALL4: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All eight directions, clockwise from `Up`.
+    pub const ALL8: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The diagonal directions, clockwise from `UpRight`.
+    pub const DIAGONALS: [Direction; 4] = [
+        Direction::UpRight,
+        Direction::DownRight,
+        Direction::DownLeft,
+        Direction::UpLeft,
+    ];
+
+    pub const fn delta(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::UpRight => Vector::new(1, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::DownRight => Vector::new(1, 1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::DownLeft => Vector::new(-1, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// Rotates clockwise by `eighths` of a full turn.
+    const fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL8[(self as usize + eighths) % 8]
+    }
+
+    /// A quarter turn clockwise.
+    pub const fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// A quarter turn anticlockwise.
+    pub const fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub const fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub const fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpRight.turn_right(), Direction::DownRight);
+        assert_eq!(Direction::DownLeft.reverse(), Direction::UpRight);
+        for direction in Direction::ALL8 {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+        }
+        assert!(Direction::DIAGONALS.iter().all(|d| d.is_diagonal()));
+        assert!(!Direction::ALL4.iter().any(|d| d.is_diagonal()));
+    }
+
+    #[test]
+    fn stepping() {
+        let origin = Point::new(0, 0);
+        assert_eq!(origin.step(Direction::Up), None);
+        assert_eq!(origin.step(Direction::Left), None);
+        assert_eq!(origin.step(Direction::DownRight), Some(Point::new(1, 1)));
+        assert_eq!(
+            Point::new(3, 4).checked_add(Direction::UpLeft.delta() * 3),
+            Some(Point::new(0, 1))
+        );
+        assert_eq!(Point::new(3, 4) - Point::new(5, 1), Vector::new(-2, 3));
+        assert_eq!(Point::new(3, 4).manhattan(Point::new(5, 1)), 5);
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::geometry::{Direction, Point, Vector};
+
+/// A rectangular grid of cells addressed by [`Point`], with `(0, 0)` top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells in row order.
+    ///
+    /// # Panics
+    ///
+    /// If `cells` does not hold exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid size mismatch");
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.y * self.width + point.x])
+    }
+
+    /// `from + vector`, if it is inside the grid.
+    pub fn offset(&self, from: Point, vector: Vector) -> Option<Point> {
+        from.checked_add(vector).filter(|to| self.contains(*to))
+    }
+
+    /// The neighbour of `from` in `direction`, if it is inside the grid.
+    pub fn step(&self, from: Point, direction: Direction) -> Option<Point> {
+        self.offset(from, direction.delta())
+    }
+
+    /// The neighbour of `from` in `direction`, wrapping around to the opposite
+    /// edge as if the grid were tiled.
+    pub fn step_wrapping(&self, from: Point, direction: Direction) -> Point {
+        let Vector { dx, dy } = direction.delta();
+        Point::new(
+            (from.x as isize + dx).rem_euclid(self.width as isize) as usize,
+            (from.y as isize + dy).rem_euclid(self.height as isize) as usize,
+        )
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Every cell with its position, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (Point::new(idx % self.width, idx / self.width), cell))
+    }
+
+    /// Every position in row order, without borrowing the grid.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// The up to four cells directly above, right of, below and left of `at`.
+    pub fn neighbors4(&self, at: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(at, &Direction::ALL4)
+    }
+
+    /// The up to eight cells surrounding `at`, including diagonals.
+    pub fn neighbors8(&self, at: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors(at, &Direction::ALL8)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        at: Point,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = (Point, &'a T)> {
+        directions
+            .iter()
+            .filter_map(move |direction| self.step(at, *direction))
+            .map(|point| (point, &self[point]))
+    }
+
+    /// Position of the first cell equal to `value`, in row order.
+    pub fn find(&self, value: &T) -> Option<Point>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .find_map(|(point, cell)| (cell == value).then_some(point))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// The grid turned a quarter turn clockwise.
+    pub fn rotate_right(&self) -> Grid<T> {
+        let cells = (0..self.width)
+            .flat_map(|y| (0..self.height).map(move |x| (x, y)))
+            .map(|(x, y)| self[Point::new(y, self.height - 1 - x)].clone())
+            .collect();
+        Grid::new(self.height, self.width, cells)
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip(&self) -> Grid<T> {
+        let cells = self
+            .positions()
+            .map(|pos| self[Point::new(self.width - 1 - pos.x, pos.y)].clone())
+            .collect();
+        Grid::new(self.width, self.height, cells)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T {
+        self.get(point).expect("point outside of grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point).expect("point outside of grid")
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                cell.fmt(f)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ParseError;
+    use crate::prelude::*;
+    use winnow::prelude::*;
+    use winnow::token::one_of;
+
+    fn parse(input: &str) -> PResult<Grid<char>> {
+        grid_of(one_of(('.', '#', 'A'..='Z'))).parse_next(&mut &*input)
+    }
+
+    #[test]
+    fn parse_and_query() {
+        let grid = parse("AB.\r\n.#C\r\nD..\r\n").unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'C'));
+        assert_eq!(grid.get(Point::new(3, 1)), None);
+        assert_eq!(grid.get(Point::new(0, 3)), None);
+        assert_eq!(grid.row(1), Some(&['.', '#', 'C'][..]));
+        assert_eq!(grid.column(0).collect::<String>(), "A.D");
+        assert_eq!(grid.find(&'D'), Some(Point::new(0, 2)));
+        assert_eq!(grid.find(&'Z'), None);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::Left), None);
+        assert_eq!(grid.step(Point::new(2, 1), Direction::Right), None);
+        assert_eq!(
+            grid.step_wrapping(Point::new(0, 0), Direction::UpLeft),
+            Point::new(2, 2)
+        );
+        assert_eq!(
+            grid.step(Point::new(2, 1), Direction::Down),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(
+            grid.offset(Point::new(0, 0), Vector::new(2, 2)),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(grid.to_string(), "AB.\n.#C\nD..\n");
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = parse("ABC\nDEF").unwrap();
+
+        let around = |x, y, n8: bool| -> String {
+            if n8 {
+                grid.neighbors8(Point::new(x, y)).map(|(_, c)| c).collect()
+            } else {
+                grid.neighbors4(Point::new(x, y)).map(|(_, c)| c).collect()
+            }
+        };
+        assert_eq!(around(1, 0, false), "CEA");
+        assert_eq!(around(1, 0, true), "CFEDA");
+        assert_eq!(around(0, 1, true), "ABE");
+    }
+
+    #[test]
+    fn transform() {
+        let grid = parse("AB.\n.#C\n").unwrap();
+        assert_eq!(grid.rotate_right().to_string(), ".A\n#B\nC.\n");
+        assert_eq!(grid.flip().to_string(), ".BA\nC#.\n");
+        assert_eq!(
+            grid.rotate_right()
+                .rotate_right()
+                .rotate_right()
+                .rotate_right(),
+            grid
+        );
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut grid = parse("..\n..\n").unwrap();
+        *grid.get_mut(Point::new(1, 1)).unwrap() = '#';
+        assert!(grid.get_mut(Point::new(2, 0)).is_none());
+        assert_eq!(grid.to_string(), "..\n.#\n");
+    }
+
+    #[test]
+    fn reject_ragged_rows() {
+        let input = "...\n..\n...\n";
+        let err = grid_of::<_, _, _>(one_of('.')).parse(input).unwrap_err();
+        let err = ParseError::new("grid", input, err.offset(), err.inner());
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected row as wide as the first");
+
+        let input = "...\n.x.\n";
+        let err = grid_of::<_, _, _>(one_of('.')).parse(input).unwrap_err();
+        let err = ParseError::new("grid", input, err.offset(), err.inner());
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "expected grid cell or newline");
+    }
+}
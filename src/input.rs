@@ -0,0 +1,120 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+/// Puzzle input text along with a name to report it by.
+pub struct Input {
+    pub name: String,
+    pub text: String,
+}
+
+/// Puzzle input opened to be read a chunk at a time rather than loaded whole.
+pub struct InputReader {
+    pub name: String,
+    pub reader: Box<dyn Read>,
+}
+
+/// Where to read a day's input from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    /// `data/dayNN.txt`, or the embedded copy with the `embed-inputs` feature.
+    Default,
+    Stdin,
+    Path(PathBuf),
+}
+
+impl Source {
+    /// Interprets a command line argument, where `-` means stdin.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+}
+
+pub fn default_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("data/day{day:02}.txt"))
+}
+
+pub fn load(day: u8, source: &Source) -> Result<Input> {
+    match source {
+        Source::Default => match embedded(day) {
+            Some(text) => Ok(Input {
+                name: format!("<embedded day{day:02}>"),
+                text: text.to_string(),
+            }),
+            None => read_file(&default_path(day)),
+        },
+        Source::Stdin => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|source| Error::Io {
+                    name: "<stdin>".to_string(),
+                    source,
+                })?;
+            Ok(Input {
+                name: "<stdin>".to_string(),
+                text,
+            })
+        }
+        Source::Path(path) => read_file(path),
+    }
+}
+
+/// Opens the input like [`load`] without reading it yet.
+pub fn open(day: u8, source: &Source) -> Result<InputReader> {
+    let open_file = |path: &Path| {
+        let name = path.display().to_string();
+        match fs::File::open(path) {
+            Ok(file) => Ok(InputReader {
+                name,
+                reader: Box::new(file),
+            }),
+            Err(source) => Err(Error::Io { name, source }),
+        }
+    };
+    match source {
+        Source::Default => match embedded(day) {
+            Some(text) => Ok(InputReader {
+                name: format!("<embedded day{day:02}>"),
+                reader: Box::new(text.as_bytes()),
+            }),
+            None => open_file(&default_path(day)),
+        },
+        Source::Stdin => Ok(InputReader {
+            name: "<stdin>".to_string(),
+            reader: Box::new(io::stdin().lock()),
+        }),
+        Source::Path(path) => open_file(path),
+    }
+}
+
+pub(crate) fn read_file(path: &Path) -> Result<Input> {
+    let name = path.display().to_string();
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Input { name, text }),
+        Err(source) => Err(Error::Io { name, source }),
+    }
+}
+
+#[cfg(feature = "embed-inputs")]
+fn embedded(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(include_str!("../data/day01.txt")),
+        2 => Some(include_str!("../data/day02.txt")),
+        3 => Some(include_str!("../data/day03.txt")),
+        4 => Some(include_str!("../data/day04.txt")),
+        5 => Some(include_str!("../data/day05.txt")),
+        6 => Some(include_str!("../data/day06.txt")),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded(_day: u8) -> Option<&'static str> {
+    None
+}
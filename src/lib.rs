@@ -1 +1,10 @@
+#![feature(iter_map_windows)]
+
+pub mod answers;
+pub mod days;
+pub mod error;
+pub mod geometry;
+pub mod grid;
+pub mod input;
 pub mod prelude;
+pub mod solution;
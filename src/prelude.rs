@@ -1 +1,231 @@
+use winnow::ascii::{dec_uint, line_ending, multispace0, space1, Uint};
+use winnow::combinator::{alt, cut_err, eof, opt, preceded, repeat_till, separated, terminated};
+use winnow::error::{AddContext, ErrMode, ErrorKind, ParserError, StrContext, StrContextValue};
+use winnow::prelude::*;
+use winnow::stream::Stream as _;
+
+use crate::grid::Grid;
+
 pub type Stream<'i> = &'i str;
+
+/// A line ending, or the end of the input for a final line without one.
+pub fn end_of_line<'i, E>(input: &mut Stream<'i>) -> PResult<Stream<'i>, E>
+where
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>,
+{
+    alt((line_ending, eof))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "newline",
+        )))
+        .parse_next(input)
+}
+
+/// The end of the input, allowing for trailing blank lines.
+pub fn end_of_input<'i, E>(input: &mut Stream<'i>) -> PResult<Stream<'i>, E>
+where
+    E: ParserError<Stream<'i>>,
+{
+    preceded(multispace0, eof).parse_next(input)
+}
+
+/// An unsigned decimal number of any width.
+pub fn number<'i, N, E>(input: &mut Stream<'i>) -> PResult<N, E>
+where
+    N: Uint,
+    E: ParserError<Stream<'i>>,
+{
+    dec_uint.parse_next(input)
+}
+
+/// One or more numbers separated by spaces or tabs, not including the line
+/// ending.
+pub fn number_line<'i, N, E>(input: &mut Stream<'i>) -> PResult<Vec<N>, E>
+where
+    N: Uint,
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>,
+{
+    separated(
+        1..,
+        number.context(StrContext::Expected(StrContextValue::Description("number"))),
+        space1,
+    )
+    .parse_next(input)
+}
+
+/// Every remaining line parsed with `line`, up to the end of the input.
+///
+/// Each line must be followed by a line ending (or the end of the input), so a
+/// line with trailing garbage is an error at the garbage rather than the end
+/// of the list.
+pub fn lines_of<'i, O, E, P>(line: P) -> impl Parser<Stream<'i>, Vec<O>, E>
+where
+    P: Parser<Stream<'i>, O, E>,
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>,
+{
+    repeat_till(0.., terminated(line, cut_err(end_of_line)), end_of_input)
+        .map(|(lines, _): (Vec<O>, _)| lines)
+}
+
+/// Two `item`s separated by `sep`, such as `47|53` or `3   4`.
+pub fn pair_sep<'i, O, S, E, P, Q>(mut item: P, mut sep: Q) -> impl Parser<Stream<'i>, (O, O), E>
+where
+    P: Parser<Stream<'i>, O, E>,
+    Q: Parser<Stream<'i>, S, E>,
+{
+    move |input: &mut Stream<'i>| {
+        let first = item.parse_next(input)?;
+        sep.parse_next(input)?;
+        let second = item.parse_next(input)?;
+        Ok((first, second))
+    }
+}
+
+/// Runs `parser` over the text up to the next blank line, which is consumed.
+///
+/// Inside the section the blank line looks like the end of the input, so
+/// [`lines_of`] and [`end_of_input`] stop there. `parser` must consume the whole
+/// section.
+pub fn section<'i, O, E, P>(mut parser: P) -> impl Parser<Stream<'i>, O, E>
+where
+    P: Parser<Stream<'i>, O, E>,
+    E: ParserError<Stream<'i>>,
+{
+    move |input: &mut Stream<'i>| {
+        let text: Stream<'i> = input;
+        let len = text
+            .split_inclusive('\n')
+            .take_while(|line| !line.trim_end_matches(['\r', '\n']).is_empty())
+            .map(str::len)
+            .sum();
+
+        let mut section = &text[..len];
+        let result = terminated(parser.by_ref(), eof).parse_next(&mut section);
+        // Leave the input where the section stopped so errors point into it.
+        *input = &text[len - section.len()..];
+        let output = result?;
+
+        opt(line_ending).parse_next(input)?;
+        Ok(output)
+    }
+}
+
+/// Blank-line separated sections, each parsed with `parser`.
+pub fn sections<'i, O, E, P>(parser: P) -> impl Parser<Stream<'i>, Vec<O>, E>
+where
+    P: Parser<Stream<'i>, O, E>,
+    E: ParserError<Stream<'i>>,
+{
+    repeat_till(1.., section(parser), end_of_input).map(|(sections, _): (Vec<O>, _)| sections)
+}
+
+/// Parses lines of `cell`s into a grid. The grid ends at the first line that
+/// does not start with a cell, and every row must have the same width.
+///
+/// Blank lines after the grid are left in the input, as another section may
+/// follow; a grid that ends the input is followed by [`end_of_input`].
+pub fn grid_of<'i, T, E, P>(mut cell: P) -> impl Parser<Stream<'i>, Grid<T>, E>
+where
+    P: Parser<Stream<'i>, T, E>,
+    E: ParserError<Stream<'i>> + AddContext<Stream<'i>, StrContext>,
+{
+    move |input: &mut Stream<'i>| {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        loop {
+            let row_start = input.checkpoint();
+            let before = cells.len();
+            while let Some(value) = opt(cell.by_ref()).parse_next(input)? {
+                cells.push(value);
+            }
+            let row_width = cells.len() - before;
+            if row_width == 0 {
+                break;
+            }
+
+            cut_err(end_of_line)
+                .context(StrContext::Expected(StrContextValue::Description(
+                    "grid cell",
+                )))
+                .parse_next(input)?;
+
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                input.reset(&row_start);
+                let err = E::from_error_kind(input, ErrorKind::Verify).add_context(
+                    input,
+                    &row_start,
+                    StrContext::Expected(StrContextValue::Description("row as wide as the first")),
+                );
+                return Err(ErrMode::Cut(err));
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            return Err(
+                ErrMode::from_error_kind(input, ErrorKind::Many).add_context(
+                    input,
+                    &input.checkpoint(),
+                    StrContext::Expected(StrContextValue::Description("grid cell")),
+                ),
+            );
+        }
+
+        Ok(Grid::new(width, height, cells))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::ParseError;
+    use winnow::error::ContextError;
+    use winnow::token::one_of;
+
+    #[test]
+    fn numbers_and_pairs() {
+        let lines = lines_of(number_line::<u8, ContextError>).parse("7 6 4\n1\t2\n\n");
+        assert_eq!(lines, Ok(vec![vec![7, 6, 4], vec![1, 2]]));
+
+        let pairs = lines_of(pair_sep(number::<u64, ContextError>, space1)).parse("3   4\r\n4 3");
+        assert_eq!(pairs, Ok(vec![(3, 4), (4, 3)]));
+    }
+
+    #[test]
+    fn split_sections() {
+        let input = "1|2\n3|4\n\n5 6\n8\n\n7\n";
+        let parsed = (
+            section(lines_of(pair_sep(number::<u32, ContextError>, '|'))),
+            sections(lines_of(number_line::<u32, ContextError>)),
+        )
+            .parse(input)
+            .unwrap();
+
+        assert_eq!(parsed.0, [(1, 2), (3, 4)]);
+        assert_eq!(parsed.1, [vec![vec![5, 6], vec![8]], vec![vec![7]]]);
+    }
+
+    #[test]
+    fn error_inside_section() {
+        let input = "1|2\n3x4\n\n5,6\n";
+        let err = (
+            section(lines_of(pair_sep(number::<u32, ContextError>, '|'))),
+            lines_of(number_line::<u32, ContextError>),
+        )
+            .parse(input)
+            .unwrap_err();
+        let err = ParseError::new("input", input, err.offset(), err.inner());
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    fn grid_of_cells() {
+        let grid = grid_of::<_, ContextError, _>(one_of(['.', '#']))
+            .parse(".#\n#.\n")
+            .unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+    }
+}
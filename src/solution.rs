@@ -0,0 +1,149 @@
+use std::{fmt, io};
+
+use winnow::prelude::*;
+
+use crate::error::{Error, ParseError, Result};
+use crate::input::{Input, InputReader};
+use crate::prelude::*;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(u64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => n.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as u64)
+            }
+        })*
+    };
+}
+
+answer_from_number!(u8, u16, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+/// A puzzle solution: the input is parsed once and shared by both parts.
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+
+    fn parse(input: &mut Stream<'_>) -> PResult<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(input: &Self::Input) -> Result<Answer>;
+
+    /// A human readable account of how the answers are reached from `text`,
+    /// for days that support `aoc run --explain`.
+    fn explain(_input: &Self::Input, _text: &str, _colour: bool) -> Result<String> {
+        Err(Error::Unimplemented)
+    }
+
+    /// Both answers from input read a chunk at a time from `reader`, named
+    /// `name` in errors, for days that support `aoc run --stream`.
+    fn stream(_name: &str, _reader: &mut dyn io::Read) -> Result<[Answer; 2]> {
+        Err(Error::Unimplemented)
+    }
+}
+
+/// Object safe view of a [`Solution`] so days can be registered side by side.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Parsed>>;
+
+    /// See [`Solution::stream`].
+    fn stream(&self, input: &mut InputReader) -> Result<[Answer; 2]>;
+}
+
+/// Parsed puzzle input, ready to solve either part.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<Answer>;
+
+    /// See [`Solution::explain`]; `text` is the input this was parsed from.
+    fn explain(&self, text: &str, colour: bool) -> Result<String>;
+}
+
+struct Solver<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for Solver<S> {
+    fn solve(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => S::part1(&self.0),
+            Part::Two => S::part2(&self.0),
+        }
+    }
+
+    fn explain(&self, text: &str, colour: bool) -> Result<String> {
+        S::explain(&self.0, text, colour)
+    }
+}
+
+impl<S: Solution + Sync + 'static> Puzzle for S
+where
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Parsed>> {
+        let parsed = S::parse.parse(input.text.as_str()).map_err(|err| {
+            Error::Parse(ParseError::new(
+                &input.name,
+                &input.text,
+                err.offset(),
+                err.inner(),
+            ))
+        })?;
+        Ok(Box::new(Solver::<S>(parsed)))
+    }
+
+    fn stream(&self, input: &mut InputReader) -> Result<[Answer; 2]> {
+        S::stream(&input.name, &mut input.reader)
+    }
+}